@@ -4,4 +4,29 @@ extern crate csv;
 pub mod utils;
 
 // Public items (traits, structs, functions) that constitute the crate's API
-pub use crate::utils::all_utils::{read_csv, print_events, filter_by_date, filter_by_string, append_to_csv, open_file_for_append, delete_events, parse_string, validate_date_format, DateComparison, StringFormat, Event};
\ No newline at end of file
+pub use crate::utils::all_utils::{read_csv, print_events, print_events_in, filter_by_date, filter_by_date_in, filter_by_string, filter_by_string_with, filter_by_string_matching, append_to_csv, open_file_for_append, delete_events, write_csv, parse_category, category_matches, category_matches_with, validate_date_format, DateComparison, StringFormat, Event};
+pub use crate::utils::timezone::{parse_time, parse_zone, resolve_time, ViewZone};
+pub use crate::utils::categories::{build_category_tree, count_categories, format_category_tree, move_categories, CategoryNode};
+pub use crate::utils::tags::{count_tags, filter_by_tags, parse_tags, retain_by_tags, TagMatch};
+pub use crate::utils::registry::{edit_distance, CategoryInfo, CategoryRegistry};
+pub use crate::utils::history::append_history;
+pub use crate::utils::table::{print_table, render_table, terminal_width, truncate_to_width, Column};
+pub use crate::utils::color::{format_colored, parse_color, print_events_colored, ColorChoice, Palette};
+pub use crate::utils::template::{print_events_template, read_templates, Template};
+pub use crate::utils::calendar::{join_months, last_day_of_month, month_grid, parse_month, shift_month};
+pub use crate::utils::charts::{count_per_day, heatmap, timeline};
+pub use crate::utils::svg::{escape_xml, render_svg};
+pub use crate::utils::html::{category_page, escape_json, render_site};
+pub use crate::utils::outline::{render_markdown, render_org};
+pub use crate::utils::stats::{render_stats, stats_json, Stats};
+pub use crate::utils::group::{group_events, GroupBy};
+pub use crate::utils::sort::{slice_events, sort_events, SortKey};
+pub use crate::utils::pager::{needs_pager, print_paged, terminal_height};
+pub use crate::utils::matcher::{MatchMode, MatchOptions, TextMatcher};
+pub use crate::utils::normalize::{fold, fold_char, fold_path, normalize, strip_accents};
+pub use crate::utils::search::{format_hit, search_events, SearchHit};
+pub use crate::utils::index::{stems, tokenize, SearchIndex};
+pub use crate::utils::dedupe::{find_duplicates, similarity, Keep};
+pub use crate::utils::relative::{business_days, calendar_diff, format_diff, format_relative, format_span, format_units, is_business_day, is_weekday, offset_date, parse_date, Offset};
+pub use crate::utils::holidays::{easter_sunday, HolidayCalendar, Rule, HOLIDAY_CATEGORY};
+pub use crate::utils::agenda::{agenda_section, group_agenda, is_yearly, upcoming, Occurrence, YEARLY_TAGS};
@@ -1,7 +1,9 @@
-mod utils;
-use utils::all_utils::{read_csv, print_events, filter_by_date, filter_by_string, append_to_csv, open_file_for_append, delete_events, parse_string, validate_date_format, DateComparison, StringFormat, Event};
+use days::{read_csv, filter_by_date_in, filter_by_string_with, filter_by_string_matching, append_to_csv, open_file_for_append, delete_events, parse_category, validate_date_format, build_category_tree, count_categories, format_category_tree, count_tags, filter_by_tags, parse_tags, retain_by_tags, TagMatch, CategoryRegistry, move_categories, write_csv, append_history, render_table, Column, print_events_colored, format_colored, ColorChoice, Palette, read_templates, Template, join_months, last_day_of_month, month_grid, parse_month, shift_month, heatmap, timeline, render_svg, render_site, render_markdown, render_org, render_stats, stats_json, Stats, group_events, GroupBy, sort_events, slice_events, SortKey, print_paged, MatchMode, MatchOptions, find_duplicates, Keep, format_relative, format_span, group_agenda, upcoming, Occurrence, Offset, format_diff, offset_date, parse_date, HolidayCalendar, search_events, format_hit, SearchIndex, terminal_width, parse_time, resolve_time, DateComparison, StringFormat, Event, ViewZone};
+use std::collections::BTreeSet;
+use std::io::{IsTerminal, Write};
 use std::path::Path;
-use clap::{App, Arg, SubCommand};
+use chrono::{Datelike, NaiveDate};
+use clap::{App, Arg, ArgMatches, SubCommand};
 
 // mostly used arg names for consistance and maintaining
 const DESCRIPTION_ARG: &str = "description";
@@ -10,6 +12,521 @@ const TODAY_ARG: &str = "today";
 const BEFORE_DATE_ARG: &str = "before-date";
 const AFTER_DATE_ARG: &str = "after-date";
 const DATE_ARG: &str = "date";
+const TZ_ARG: &str = "tz";
+const TIME_ARG: &str = "time";
+const TIMEZONE_ARG: &str = "timezone";
+const TAG_ARG: &str = "tag";
+const ANY_TAG_ARG: &str = "any-tag";
+const ALL_TAGS_ARG: &str = "all-tags";
+const NO_TAG_ARG: &str = "no-tag";
+const TABLE_ARG: &str = "table";
+const COLUMNS_ARG: &str = "columns";
+const COLOR_ARG: &str = "color";
+const FORMAT_ARG: &str = "format";
+const OUT_ARG: &str = "out";
+const GROUP_BY_ARG: &str = "group-by";
+const COUNT_ARG: &str = "count";
+const SORT_ARG: &str = "sort";
+const REVERSE_ARG: &str = "reverse";
+const LIMIT_ARG: &str = "limit";
+const OFFSET_ARG: &str = "offset";
+const TAIL_ARG: &str = "tail";
+const MATCH_ARG: &str = "match";
+const CASE_SENSITIVE_ARG: &str = "case-sensitive";
+const IGNORE_ACCENTS_ARG: &str = "ignore-accents";
+const RELATIVE_ARG: &str = "relative";
+const BUSINESS_ARG: &str = "business";
+const HOLIDAYS_ARG: &str = "holidays";
+
+// tag filter args shared by list and delete
+const TAG_FILTER_ARGS: &[&str] = &[TAG_ARG, ANY_TAG_ARG, ALL_TAGS_ARG, NO_TAG_ARG];
+
+// args of delete that choose events, without any of them nothing is deleted
+const DELETE_FILTER_ARGS: &[&str] = &["all", TODAY_ARG, BEFORE_DATE_ARG, AFTER_DATE_ARG, DATE_ARG, CATEGORY_ARG, DESCRIPTION_ARG, TAG_ARG, ANY_TAG_ARG, ALL_TAGS_ARG, NO_TAG_ARG];
+
+// args of list that choose events, without any of them all events are listed
+const LIST_FILTER_ARGS: &[&str] = &[TODAY_ARG, BEFORE_DATE_ARG, AFTER_DATE_ARG, DATE_ARG, CATEGORY_ARG, DESCRIPTION_ARG, TAG_ARG, ANY_TAG_ARG, ALL_TAGS_ARG, NO_TAG_ARG];
+
+// get zone for showing the events from --tz, exit program if zone is unknown
+fn view_zone(matches: &ArgMatches) -> ViewZone {
+    match matches.value_of(TZ_ARG) {
+        Some(name) => ViewZone::parse(name).unwrap_or_else(|err| {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }),
+        None => ViewZone::Local,
+    }
+}
+
+// --tz arg shared by list and delete
+fn tz_arg() -> Arg<'static> {
+    Arg::new(TZ_ARG)
+        .long(TZ_ARG)
+        .takes_value(true)
+        .value_name("ZONE")
+        .required(false)
+        .help("Show times and today in given IANA time zone, for example Europe/Helsinki.\nNo zone: use local time zone")
+}
+
+// --color arg shared by list and delete
+fn color_arg() -> Arg<'static> {
+    Arg::new(COLOR_ARG)
+        .long(COLOR_ARG)
+        .takes_value(true)
+        .value_name("WHEN")
+        .possible_values(["auto", "always", "never"])
+        .default_value("auto")
+        .help("Color output by category and date. Auto uses colors on terminal when NO_COLOR is not set")
+}
+
+// get palette from --color and colors in categories file, exit program if choice is invalid
+fn palette(matches: &ArgMatches, registry: &CategoryRegistry) -> Palette {
+    let choice = ColorChoice::parse(matches.value_of(COLOR_ARG).unwrap_or("auto")).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    });
+    Palette::new(choice.enabled(), registry)
+}
+
+// tag filter args shared by list and delete
+fn tag_args() -> [Arg<'static>; 4] {
+    [
+        Arg::new(TAG_ARG)
+            .long(TAG_ARG)
+            .takes_value(true)
+            .multiple_occurrences(true)
+            .value_name("TAG")
+            .help("Choose events with given tag. Can be given multiple times"),
+        Arg::new(ANY_TAG_ARG)
+            .long(ANY_TAG_ARG)
+            .takes_value(true)
+            .value_name("TAG[,TAG...]")
+            .help("Choose events with any of the tags separated by commas"),
+        Arg::new(ALL_TAGS_ARG)
+            .long(ALL_TAGS_ARG)
+            .takes_value(true)
+            .value_name("TAG[,TAG...]")
+            .help("Choose events with all of the tags separated by commas"),
+        Arg::new(NO_TAG_ARG)
+            .long(NO_TAG_ARG)
+            .takes_value(true)
+            .min_values(0)
+            .value_name("TAG[,TAG...]")
+            .help("Choose events without any of the tags separated by commas.\nNo tags: choose events without tags"),
+    ]
+}
+
+// category filter args shared by views that draw the chosen events
+fn category_filter_args() -> [Arg<'static>; 2] {
+    [
+        Arg::new(CATEGORY_ARG)
+            .long(CATEGORY_ARG)
+            .takes_value(true)
+            .value_name("CAT[,CAT...]")
+            .required(false)
+            .help("Show only events of one or multiple categories separated by commas"),
+        Arg::new("exclude")
+            .long("exclude")
+            .takes_value(false)
+            .requires(CATEGORY_ARG)
+            .help("Exclude the category filter."),
+    ]
+}
+
+// choose events with category and tag filter args, all events if none of them is given
+fn choose_by_category_and_tags(matches: &ArgMatches, orig_events: &[Event], registry: &CategoryRegistry) -> Vec<Event> {
+    if !matches.is_present(CATEGORY_ARG) && !TAG_FILTER_ARGS.iter().any(|arg| matches.is_present(arg)) {
+        return orig_events.to_vec();
+    }
+    let mut chosen_events = Vec::new();
+    if let Some(categories) = matches.value_of(CATEGORY_ARG) {
+        filter_by_string_with(orig_events, &mut chosen_events, categories, matches.is_present("exclude"), true, registry);
+    }
+    filter_tags(matches, orig_events, &mut chosen_events, matches.is_present(CATEGORY_ARG));
+    chosen_events
+}
+
+// true if some of the args choosing events, other than all-tags and no-tag, is present
+fn choosing_args_present(matches: &ArgMatches, args: &[&str]) -> bool {
+    args.iter().filter(|arg| ![ALL_TAGS_ARG, NO_TAG_ARG].contains(arg)).any(|arg| matches.is_present(arg))
+}
+
+// add events matching the tag filter args to results. All-tags and no-tag only narrow the results
+// when other filters have chosen events, otherwise they choose from all events
+fn filter_tags(matches: &ArgMatches, orig_events: &[Event], result_events: &mut Vec<Event>, others_present: bool) {
+    let mut narrow = others_present;
+    if let Some(tags) = matches.values_of(TAG_ARG) {
+        let tags = parse_tags(&tags.collect::<Vec<_>>().join(","));
+        filter_by_tags(orig_events, result_events, &tags, TagMatch::Any);
+        narrow = true;
+    }
+    if let Some(tags) = matches.value_of(ANY_TAG_ARG) {
+        filter_by_tags(orig_events, result_events, &parse_tags(tags), TagMatch::Any);
+        narrow = true;
+    }
+    let all_tags = matches.value_of(ALL_TAGS_ARG).map(parse_tags);
+    let no_tags = matches.is_present(NO_TAG_ARG).then(|| parse_tags(&matches.values_of(NO_TAG_ARG).map(|tags| tags.collect::<Vec<_>>().join(",")).unwrap_or_default()));
+    for (tags, mode) in [(all_tags, TagMatch::All), (no_tags, TagMatch::None)] {
+        if let Some(tags) = tags {
+            if narrow {
+                retain_by_tags(result_events, &tags, mode);
+            } else {
+                filter_by_tags(orig_events, result_events, &tags, mode);
+                narrow = true;
+            }
+        }
+    }
+}
+
+// parse category arg to category path, commas are accepted like in older versions. Exit program if invalid
+fn parse_category_arg(category: &str) -> Vec<String> {
+    let lower_category = category.to_lowercase().replace(',', "/");
+    match parse_category(&lower_category, '/') {
+        Ok(category) => category,
+        Err(err) => {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    }
+}
+
+// date, category and description filter args shared by list and stats
+fn list_filter_args() -> [Arg<'static>; 7] {
+    [
+        Arg::new(TODAY_ARG)
+            .long(TODAY_ARG)
+            .takes_value(false)
+            .required(false)
+            .help("Choose events on todays date."),
+        Arg::new(BEFORE_DATE_ARG)
+            .long(BEFORE_DATE_ARG)
+            .takes_value(true)
+            .value_name("YYYY-MM-DD")
+            .required(false)
+            .help("Choose events before given date\nGive date in format: YYYY-MM-DD"),
+        Arg::new(AFTER_DATE_ARG)
+            .long(AFTER_DATE_ARG)
+            .takes_value(true)
+            .value_name("YYYY-MM-DD")
+            .required(false)
+            .help("Choose events after given date\nGive date in format: YYYY-MM-DD"),
+        Arg::new(DATE_ARG)
+            .long(DATE_ARG)
+            .takes_value(true)
+            .value_name("YYYY-MM-DD")
+            .required(false)
+            .help("Choose events on given date\nGive date in format: YYYY-MM-DD"),
+        Arg::new(CATEGORY_ARG)
+            .long(CATEGORY_ARG)
+            .takes_value(true)
+            .value_name("CAT[,CAT...]")
+            .required(false)
+            .help("Filter one or multiple categories separated by commas"),
+        Arg::new("exclude")
+            .long("exclude")
+            .takes_value(false)
+            .requires(CATEGORY_ARG)
+            .help("Exclude the category filter."),
+        Arg::new(DESCRIPTION_ARG)
+            .long(DESCRIPTION_ARG)
+            .takes_value(true)
+            .value_name("DESCRIPTION")
+            .required(false)
+            .help("Choose events with start of description value"),
+    ]
+}
+
+// match mode args shared by list, stats and delete
+fn match_args() -> [Arg<'static>; 3] {
+    [
+        Arg::new(MATCH_ARG)
+            .long(MATCH_ARG)
+            .takes_value(true)
+            .possible_values(["prefix", "contains", "exact", "regex", "glob"])
+            .help("How description and category filters match. No mode: prefix.\nOther modes than prefix match the whole category path like microsoft/dotnet"),
+        Arg::new(CASE_SENSITIVE_ARG)
+            .long(CASE_SENSITIVE_ARG)
+            .takes_value(false)
+            .help("Match description and category filters case sensitively."),
+        Arg::new(IGNORE_ACCENTS_ARG)
+            .long(IGNORE_ACCENTS_ARG)
+            .takes_value(false)
+            .help("Ignore accents in description and category filters, naytto matches Näyttö."),
+    ]
+}
+
+// get match options from match args, exit program if mode is invalid
+fn match_options(matches: &ArgMatches) -> MatchOptions {
+    let mode = MatchMode::parse(matches.value_of(MATCH_ARG).unwrap_or("prefix")).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    });
+    MatchOptions { mode, case_sensitive: matches.is_present(CASE_SENSITIVE_ARG), ignore_accents: matches.is_present(IGNORE_ACCENTS_ARG) }
+}
+
+// filter by description or category with the match args, exit program if the pattern is invalid
+fn filter_strings(matches: &ArgMatches, orig_events: &[Event], result_events: &mut Vec<Event>, input: &str, excluded: bool, category: bool, registry: &CategoryRegistry) {
+    if let Err(err) = filter_by_string_matching(orig_events, result_events, input, excluded, category, registry, match_options(matches)) {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    }
+}
+
+// choose events with the list filter args, all events if none of them is given. Exit program if a date is invalid
+fn choose_list_events(matches: &ArgMatches, orig_events: &[Event], registry: &CategoryRegistry, zone: &ViewZone) -> Vec<Event> {
+    let mut result_events = Vec::new();
+    // without filter args present, choose all events
+    if !LIST_FILTER_ARGS.iter().any(|arg| matches.is_present(arg)) {
+        if let Err(err) = filter_by_date_in(orig_events, &mut result_events, "", DateComparison::All, zone) {
+            eprintln!("Error parsing date: {}", err);
+            std::process::exit(1);
+        }
+    }
+
+    // add todays date matches on results
+    if matches.is_present(TODAY_ARG) {
+        if let Err(err) = filter_by_date_in(orig_events, &mut result_events, "", DateComparison::Today, zone) {
+            eprintln!("Error parsing date: {}", err);
+            std::process::exit(1);
+        }
+    }
+    // filtered with both after and before date
+    if matches.is_present(BEFORE_DATE_ARG) && matches.is_present(AFTER_DATE_ARG) {
+        if let (Some(date1), Some(date2)) = (
+            matches.value_of(BEFORE_DATE_ARG),
+            matches.value_of(AFTER_DATE_ARG),
+        ) {
+            if validate_date_format(date1) {
+                if validate_date_format(date2) {
+                    let date_range = format!("{},{}", date1, date2);
+                    if let Err(err) = filter_by_date_in(orig_events, &mut result_events, &date_range, DateComparison::BeforeAfter, zone) {
+                        eprintln!("Error parsing date: {}", err);
+                        std::process::exit(1);
+                    }
+                } else {
+                    eprintln!("Error parsing after-date. Use format YYYY-mm-dd.");
+                    std::process::exit(1);
+                }
+            } else {
+                eprintln!("Error parsing before-date. Use format YYYY-mm-dd.");
+                std::process::exit(1);
+            }
+        }
+    // add before given date matches on results
+    } else if let Some(date) = matches.value_of(BEFORE_DATE_ARG) {
+        if validate_date_format(date) {
+            if let Err(err) = filter_by_date_in(orig_events, &mut result_events, date, DateComparison::Before, zone) {
+                eprintln!("Error parsing date: {}", err);
+                std::process::exit(1);
+            }
+        } else {
+            eprintln!("Error parsing date. Use format YYYY-mm-dd.");
+            std::process::exit(1);
+        }
+    // add after given date matches on results
+    } else if let Some(date) = matches.value_of(AFTER_DATE_ARG) {
+        if validate_date_format(date) {
+            if let Err(err) = filter_by_date_in(orig_events, &mut result_events, date, DateComparison::After, zone) {
+                eprintln!("Error parsing date: {}", err);
+                std::process::exit(1);
+            }
+        } else {
+            eprintln!("Error parsing date. Use format YYYY-mm-dd.");
+            std::process::exit(1);
+        }
+    }
+
+    // add given date matches on results
+    if let Some(date) = matches.value_of(DATE_ARG) {
+        if validate_date_format(date) {
+            if let Err(err) = filter_by_date_in(orig_events, &mut result_events, date, DateComparison::Exact, zone) {
+                eprintln!("Error parsing date: {}", err);
+                std::process::exit(1);
+            }
+        } else {
+            eprintln!("Error parsing date. Use format YYYY-mm-dd.");
+            std::process::exit(1);
+        }
+
+    }
+
+    // add given category/categories matches to results
+    if let Some(description) = matches.value_of(DESCRIPTION_ARG) {
+        filter_strings(matches, orig_events, &mut result_events, description, false, false, registry);
+    }
+
+    // add given category/categories matches to results, depending if excluded or not
+    if let Some(categories) = matches.value_of(CATEGORY_ARG) {
+        let exclude = matches.is_present("exclude");
+        filter_strings(matches, orig_events, &mut result_events, categories, exclude, true, registry);
+    }
+
+    // add given tag matches to results
+    filter_tags(matches, orig_events, &mut result_events, choosing_args_present(matches, LIST_FILTER_ARGS));
+    result_events
+}
+
+// parse count arg like --limit, exit program if it is not a number
+fn count_arg(matches: &ArgMatches, name: &str) -> Option<usize> {
+    matches.value_of(name).map(|value| {
+        value.parse().unwrap_or_else(|_| {
+            eprintln!("Error parsing --{}. Give a non-negative number.", name);
+            std::process::exit(1);
+        })
+    })
+}
+
+// update search index with the change if the index is in use, exit program on error
+fn update_index(index_path: &str, change: impl FnOnce(&mut SearchIndex)) {
+    match SearchIndex::read(index_path) {
+        Ok(Some(mut index)) => {
+            change(&mut index);
+            if let Err(err) = index.write(index_path) {
+                eprintln!("Error writing search index: {}", err);
+                std::process::exit(1);
+            }
+        }
+        Ok(None) => {}
+        Err(err) => {
+            eprintln!("Error reading search index: {}", err);
+            std::process::exit(1);
+        }
+    }
+}
+
+// ask which event of a duplicate group to keep, None skips the group
+fn ask_survivor(count: usize) -> Option<usize> {
+    loop {
+        print!("Keep which (1-{}, s to skip)? ", count);
+        let _ = std::io::stdout().flush();
+        let mut answer = String::new();
+        // end of input skips like s
+        if std::io::stdin().read_line(&mut answer).unwrap_or(0) == 0 {
+            return None;
+        }
+        match answer.trim() {
+            "s" | "" => return None,
+            number => match number.parse::<usize>() {
+                Ok(number) if (1..=count).contains(&number) => return Some(number - 1),
+                _ => println!("Give a number from 1 to {} or s.", count),
+            },
+        }
+    }
+}
+
+// business days arg shared by until and since
+fn business_arg() -> Arg<'static> {
+    Arg::new(BUSINESS_ARG)
+        .long(BUSINESS_ARG)
+        .takes_value(false)
+        .required(false)
+        .help("Count business days, monday to friday without the holidays of --holidays, instead of calendar days.")
+}
+
+// events chosen by until and since selector: events with the id, or events matching the description
+fn select_events(matches: &ArgMatches, orig_events: &[Event], selector: &str, registry: &CategoryRegistry) -> Vec<Event> {
+    let by_id: Vec<Event> = orig_events.iter().filter(|event| event.id() == selector.to_lowercase()).cloned().collect();
+    if !by_id.is_empty() {
+        return by_id;
+    }
+    let mut result_events = Vec::new();
+    filter_strings(matches, orig_events, &mut result_events, selector, false, false, registry);
+    result_events
+}
+
+// holiday country arg shared by commands showing events or counting business days
+fn holidays_arg() -> Arg<'static> {
+    Arg::new(HOLIDAYS_ARG)
+        .long(HOLIDAYS_ARG)
+        .takes_value(true)
+        .value_name("COUNTRY")
+        .required(false)
+        .help("Public holidays of the country, for example FI.\nShown as events in category holiday and skipped in business days")
+}
+
+// get holiday calendar from holidays arg, exit program if the country is unknown
+fn holiday_calendar(matches: &ArgMatches) -> Option<HolidayCalendar> {
+    matches.value_of(HOLIDAYS_ARG).map(|country| {
+        HolidayCalendar::new(country).unwrap_or_else(|err| {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        })
+    })
+}
+
+// range and anniversary args shared by upcoming and agenda
+fn upcoming_args(within: &'static str) -> [Arg<'static>; 3] {
+    [
+        Arg::new("within")
+            .long("within")
+            .takes_value(true)
+            .value_name("OFFSET")
+            .default_value(within)
+            .help("Show events from today to today plus offset, like 14d, 2w, 3m or 1y."),
+        Arg::new("anniversaries")
+            .long("anniversaries")
+            .takes_value(false)
+            .required(false)
+            .help("Show anniversaries of all events. Without: only of events tagged yearly or anniversary"),
+        holidays_arg(),
+    ]
+}
+
+// upcoming events and anniversaries chosen with upcoming args, exit program if the range is invalid
+fn choose_upcoming(matches: &ArgMatches, orig_events: &[Event], registry: &CategoryRegistry, zone: &ViewZone) -> Vec<Occurrence> {
+    let today = zone.today();
+    let holidays = holiday_calendar(matches);
+    let end = Offset::parse(matches.value_of("within").unwrap_or("14d"))
+        .and_then(|offset| offset.apply_with(today, holidays.as_ref()).ok_or_else(|| "Error: --within is out of range.".to_string()))
+        .unwrap_or_else(|err| {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        });
+    let chosen_events = choose_by_category_and_tags(matches, orig_events, registry);
+    let mut occurrences = upcoming(&chosen_events, today, end, matches.is_present("anniversaries"), zone);
+    // holidays are not filtered and have no anniversaries
+    if let Some(holidays) = holidays {
+        occurrences.extend(holidays.events_between(today, end).into_iter().map(|event| Occurrence { date: event.date(), event, years: None }));
+        occurrences.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.event.cmp(&b.event)));
+    }
+    occurrences
+}
+
+// occurrence as event line with the years of an anniversary
+fn format_occurrence(occurrence: &Occurrence, zone: &ViewZone, palette: &Palette, today: NaiveDate) -> String {
+    let line = format_colored(&occurrence.shown_event(), zone, palette, today);
+    match occurrence.years {
+        Some(years) => format!("{} ({} {})", line, years, if years == 1 { "year" } else { "years" }),
+        None => line,
+    }
+}
+
+// positional date arg of the date commands, YYYY-mm-dd or today
+fn day_arg(name: &'static str, index: usize) -> Arg<'static> {
+    Arg::new(name)
+        .index(index)
+        .takes_value(true)
+        .value_name("DATE")
+        .required(true)
+        .help("Date in format YYYY-mm-dd, or today.")
+}
+
+// get date from date arg, exit program if it is invalid
+fn date_arg(matches: &ArgMatches, name: &str, zone: &ViewZone) -> NaiveDate {
+    parse_date(matches.value_of(name).unwrap_or_default(), zone.today()).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        std::process::exit(1);
+    })
+}
+
+// dry-run arg shared by category rename and merge
+fn dry_run_arg() -> Arg<'static> {
+    Arg::new("dry-run")
+        .long("dry-run")
+        .takes_value(false)
+        .required(false)
+        .help("List changed events without changing them.")
+}
 
 fn main() {
     let matches = App::new("NinasAlmanak")
@@ -19,65 +536,120 @@ fn main() {
                     .subcommand(
                         SubCommand::with_name("list")
                             .about("Print all events if no filters are specified\n\tcargo run -- list")
+                            .args(list_filter_args())
+                            .args(match_args())
+                            .args(tag_args())
+                            .arg(tz_arg())
+                            .arg(color_arg())
+                            .arg(holidays_arg())
                             .arg(
-                                Arg::new(TODAY_ARG)
-                                    .long(TODAY_ARG)
-                                    .takes_value(false)
-                                    .required(false)
-                                    .help("Choose events on todays date.")
+                                Arg::new(TABLE_ARG)
+                                .long(TABLE_ARG)
+                                .takes_value(false)
+                                .required(false)
+                                .help("Print events as a table fitting the terminal width.")
                             )
                             .arg(
-                                Arg::new(BEFORE_DATE_ARG)
-                                .long(BEFORE_DATE_ARG)
+                                Arg::new(COLUMNS_ARG)
+                                .long(COLUMNS_ARG)
                                 .takes_value(true)
-                                .value_name("YYYY-MM-DD")
+                                .value_name("COL[,COL...]")
                                 .required(false)
-                                .help("Choose events before given date\nGive date in format: YYYY-MM-DD")
+                                .help("Choose table columns separated by commas: date, category, description, tags, id, relative.\nImplies --table")
                             )
                             .arg(
-                                Arg::new(AFTER_DATE_ARG)
-                                .long(AFTER_DATE_ARG)
+                                Arg::new(FORMAT_ARG)
+                                .long(FORMAT_ARG)
                                 .takes_value(true)
-                                .value_name("YYYY-MM-DD")
+                                .value_name("TEMPLATE|NAME")
                                 .required(false)
-                                .help("Choose events after given date\nGive date in format: YYYY-MM-DD")
+                                .conflicts_with_all(&[TABLE_ARG, COLUMNS_ARG])
+                                .help("Print events with template or template named in templates.csv, for example:\n\t'{date:%d.%m.%Y} [{category}] {description}{?tags} {tags}{/tags}'\nFields: date, time, datetime, timezone, description, category, primary, secondary, tags, id, weekday")
                             )
                             .arg(
-                                Arg::new(DATE_ARG)
-                                .long(DATE_ARG)
+                                Arg::new(GROUP_BY_ARG)
+                                .long(GROUP_BY_ARG)
                                 .takes_value(true)
-                                .value_name("YYYY-MM-DD")
+                                .possible_values(["year", "month", "weekday", "category"])
                                 .required(false)
-                                .help("Choose events on given date\nGive date in format: YYYY-MM-DD")
+                                .help("Print events in groups with headers and counts.")
                             )
                             .arg(
-                                Arg::new(CATEGORY_ARG)
-                                .long(CATEGORY_ARG)
-                                .takes_value(true)
-                                .value_name("CAT[,CAT...]")
+                                Arg::new(COUNT_ARG)
+                                .long(COUNT_ARG)
+                                .takes_value(false)
                                 .required(false)
-                                .help("Filter one or multiple categories separated by commas")
+                                .help("Print only count of events, or counts of groups with --group-by.")
                             )
                             .arg(
-                                Arg::new("exclude")
-                                .long("exclude")
+                                Arg::new(RELATIVE_ARG)
+                                .long(RELATIVE_ARG)
                                 .takes_value(false)
-                                .requires(CATEGORY_ARG)
-                                .help("Exclude the category filter.")
+                                .required(false)
+                                .conflicts_with(FORMAT_ARG)
+                                .help("Show dates also relative to today, like in 12 days or 3 years 2 months ago.")
                             )
                             .arg(
-                                Arg::new(DESCRIPTION_ARG)
-                                .long(DESCRIPTION_ARG)
+                                Arg::new(SORT_ARG)
+                                .long(SORT_ARG)
                                 .takes_value(true)
-                                .value_name("DESCRIPTION")
+                                .possible_values(["date", "description", "category"])
+                                .default_value("date")
+                                .help("Sort events, inside groups with --group-by.")
+                            )
+                            .arg(
+                                Arg::new(REVERSE_ARG)
+                                .long(REVERSE_ARG)
+                                .takes_value(false)
+                                .required(false)
+                                .help("Reverse the sort order.")
+                            )
+                            .arg(
+                                Arg::new(LIMIT_ARG)
+                                .long(LIMIT_ARG)
+                                .takes_value(true)
+                                .value_name("N")
+                                .required(false)
+                                .help("Print at most N events.")
+                            )
+                            .arg(
+                                Arg::new(OFFSET_ARG)
+                                .long(OFFSET_ARG)
+                                .takes_value(true)
+                                .value_name("N")
+                                .required(false)
+                                .help("Skip first N events.")
+                            )
+                            .arg(
+                                Arg::new(TAIL_ARG)
+                                .long(TAIL_ARG)
+                                .takes_value(true)
+                                .value_name("N")
                                 .required(false)
-                                .help("Choose events with start of description value")
+                                .conflicts_with_all(&[LIMIT_ARG, OFFSET_ARG])
+                                .help("Print only last N events.")
                             )
 
                     )
                     .subcommand(
                         SubCommand::with_name("add")
-                            .about("Add event in used csv file.\n\tcargo run -- add\n\nNo given date: use todays date.")
+                            .about("Add event in used csv file.\n\tcargo run -- add\n\nNo given date: use todays date.\nWith DATE and OFFSET print the date moved by the offset instead:\n\tcargo run -- add 2023-11-14 +3w")
+                            .arg(
+                                day_arg("day", 1)
+                                .required(false)
+                                .requires("offset")
+                            )
+                            .arg(
+                                Arg::new("offset")
+                                .index(2)
+                                .takes_value(true)
+                                .value_name("OFFSET")
+                                .allow_hyphen_values(true)
+                                .requires("day")
+                                .conflicts_with_all(&[DESCRIPTION_ARG, DATE_ARG, CATEGORY_ARG, TIME_ARG, TAG_ARG])
+                                .help("Offset to add to DATE, like +10d, -2w, +3m, 1y or +5b for business days.")
+                            )
+                            .arg(holidays_arg().requires("offset"))
                             .arg(
                                 Arg::new(DATE_ARG)
                                 .long(DATE_ARG)
@@ -91,16 +663,48 @@ fn main() {
                                 .long(DESCRIPTION_ARG)
                                 .takes_value(true)
                                 .value_name("DESCRIPTION")
-                                .required(true)
+                                .required_unless_present("offset")
                                 .help("Add event description")
                             )
                             .arg(
                                 Arg::new(CATEGORY_ARG)
                                 .long(CATEGORY_ARG)
                                 .takes_value(true)
-                                .value_name("PRIMARY[,SECONDARY]")
+                                .value_name("CATEGORY[/SUBCATEGORY...]")
                                 .required(false)
-                                .help("Add event category/categories.\nGive category in format: \n\t<primary_category[,secondary_category]>")
+                                .help("Add event category of any depth.\nGive category in format: \n\t<category[/subcategory/...]>\nLevels can be also separated with commas")
+                            )
+                            .arg(
+                                Arg::new(TIME_ARG)
+                                .long(TIME_ARG)
+                                .takes_value(true)
+                                .value_name("HH:MM")
+                                .required(false)
+                                .help("Add event time of day\nGive time in format: HH:MM")
+                            )
+                            .arg(
+                                Arg::new(TAG_ARG)
+                                .long(TAG_ARG)
+                                .takes_value(true)
+                                .multiple_occurrences(true)
+                                .value_name("TAG")
+                                .required(false)
+                                .help("Add event tag, for example #release. Can be given multiple times")
+                            )
+                            .arg(
+                                Arg::new("allow-unknown")
+                                .long("allow-unknown")
+                                .takes_value(false)
+                                .required(false)
+                                .help("Add event even if category is not declared in categories file.")
+                            )
+                            .arg(
+                                Arg::new(TIMEZONE_ARG)
+                                .long(TIMEZONE_ARG)
+                                .takes_value(true)
+                                .value_name("ZONE")
+                                .requires(TIME_ARG)
+                                .help("Time zone of the event time, for example Europe/Helsinki.\nNo zone: use local time zone")
                             )
                     )
                     .subcommand(
@@ -118,7 +722,7 @@ fn main() {
                                 .long(DESCRIPTION_ARG)
                                 .takes_value(true)
                                 .required(false)
-                                .help("Filter to delete by start of the description.")
+                                .help("Filter to delete by start of the description, or as given with --match.")
                             )
                             .arg(
                                 Arg::new(DATE_ARG)
@@ -153,7 +757,7 @@ fn main() {
                                 .long(CATEGORY_ARG)
                                 .takes_value(true)
                                 .required(false)
-                                .help("Filter to delete by start of the primary or secondary category.")
+                                .help("Filter to delete by start of the primary or secondary category, or as given with --match.")
                             )
                             .arg(
                                 Arg::new("all")
@@ -162,6 +766,268 @@ fn main() {
                                 .required(false)
                                 .help("Filter to delete every event.")
                             )
+                            .args(match_args())
+                            .args(tag_args())
+                            .arg(tz_arg())
+                            .arg(color_arg())
+                    )
+                    .subcommand(
+                        SubCommand::with_name("until")
+                            .about("Print days until events or a date.\n\tcargo run -- until 2026-12-24")
+                            .arg(
+                                Arg::new("selector")
+                                .takes_value(true)
+                                .value_name("SELECTOR")
+                                .required(true)
+                                .help("Date as YYYY-mm-dd, event id or start of the description, or as given with --match.")
+                            )
+                            .arg(business_arg())
+                            .arg(holidays_arg())
+                            .args(match_args())
+                            .arg(tz_arg())
+                            .arg(color_arg())
+                    )
+                    .subcommand(
+                        SubCommand::with_name("since")
+                            .about("Print days since events or a date.\n\tcargo run -- since \"macOS 14\" --business")
+                            .arg(
+                                Arg::new("selector")
+                                .takes_value(true)
+                                .value_name("SELECTOR")
+                                .required(true)
+                                .help("Date as YYYY-mm-dd, event id or start of the description, or as given with --match.")
+                            )
+                            .arg(business_arg())
+                            .arg(holidays_arg())
+                            .args(match_args())
+                            .arg(tz_arg())
+                            .arg(color_arg())
+                    )
+                    .subcommand(
+                        SubCommand::with_name("upcoming")
+                            .about("Print events and anniversaries coming up from today.\n\tcargo run -- upcoming --within 14d")
+                            .args(upcoming_args("14d"))
+                            .args(category_filter_args())
+                            .args(tag_args())
+                            .arg(tz_arg())
+                            .arg(color_arg())
+                    )
+                    .subcommand(
+                        SubCommand::with_name("agenda")
+                            .about("Print coming events under Today, Tomorrow, This week and Later.\n\tcargo run -- agenda")
+                            .args(upcoming_args("30d"))
+                            .args(category_filter_args())
+                            .args(tag_args())
+                            .arg(tz_arg())
+                            .arg(color_arg())
+                    )
+                    .subcommand(
+                        SubCommand::with_name("diff")
+                            .about("Print difference of two dates in days, weeks, months and years.\n\tcargo run -- diff 2023-11-14 today")
+                            .arg(day_arg("from", 1))
+                            .arg(day_arg("to", 2))
+                            .arg(holidays_arg())
+                            .arg(tz_arg())
+                    )
+                    .subcommand(
+                        SubCommand::with_name("weekday")
+                            .about("Print weekday of the date.\n\tcargo run -- weekday 2023-11-14")
+                            .arg(day_arg("day", 1))
+                            .arg(tz_arg())
+                    )
+                    .subcommand(
+                        SubCommand::with_name("week")
+                            .about("Print ISO week of the date.\n\tcargo run -- week 2023-11-14")
+                            .arg(day_arg("day", 1))
+                            .arg(tz_arg())
+                    )
+                    .subcommand(
+                        SubCommand::with_name("dedupe")
+                            .about("Find duplicate events and keep one of each group. Duplicates are on the same day with similar descriptions, or have the same description within some days.\n\tcargo run -- dedupe --keep longest")
+                            .arg(
+                                Arg::new("keep")
+                                .long("keep")
+                                .takes_value(true)
+                                .possible_values(["first", "last", "longest"])
+                                .required(false)
+                                .help("Event to keep of each group. first and last are in file order, longest has the longest description. No keep: ask for each group")
+                            )
+                            .arg(
+                                Arg::new("similarity")
+                                .long("similarity")
+                                .takes_value(true)
+                                .value_name("0-1")
+                                .default_value("0.8")
+                                .help("How similar descriptions on the same day must be, 1 is equal ignoring case.")
+                            )
+                            .arg(
+                                Arg::new("within")
+                                .long("within")
+                                .takes_value(true)
+                                .value_name("DAYS")
+                                .default_value("3")
+                                .help("Days between events with the same description that are duplicates.")
+                            )
+                            .arg(dry_run_arg())
+                            .arg(tz_arg())
+                            .arg(color_arg())
+                    )
+                    .subcommand(
+                        SubCommand::with_name("categories")
+                            .about("Print all used categories with event counts.")
+                            .arg(
+                                Arg::new("tree")
+                                .long("tree")
+                                .takes_value(false)
+                                .required(false)
+                                .help("Print categories as a tree with counts per level.")
+                            )
+                    )
+                    .subcommand(
+                        SubCommand::with_name("category")
+                            .about("Rename or merge categories in all events.")
+                            .subcommand(
+                                SubCommand::with_name("rename")
+                                    .about("Rename category, subcategories are moved with it.\n\tcargo run -- category rename apple vendor/apple")
+                                    .arg(Arg::new("old").required(true).value_name("OLD").help("Category to rename"))
+                                    .arg(Arg::new("new").required(true).value_name("NEW").help("New name of the category"))
+                                    .arg(dry_run_arg())
+                            )
+                            .subcommand(
+                                SubCommand::with_name("merge")
+                                    .about("Merge categories into last given category.\n\tcargo run -- category merge ms msft microsoft")
+                                    .arg(
+                                        Arg::new("categories")
+                                        .required(true)
+                                        .multiple_values(true)
+                                        .min_values(3)
+                                        .value_name("CATEGORY")
+                                        .help("Categories to merge followed by the category to merge into")
+                                    )
+                                    .arg(dry_run_arg())
+                            )
+                    )
+                    .subcommand(
+                        SubCommand::with_name("cal")
+                            .about("Print month calendar with days with events marked by '*' and the events below.\n\tcargo run -- cal 2023-11")
+                            .arg(
+                                Arg::new("month")
+                                .takes_value(true)
+                                .value_name("YYYY-MM")
+                                .required(false)
+                                .help("Month to show. No month: use current month")
+                            )
+                            .arg(
+                                Arg::new("three")
+                                .short('3')
+                                .takes_value(false)
+                                .required(false)
+                                .help("Show previous, current and next month.")
+                            )
+                            .args(category_filter_args())
+                            .args(tag_args())
+                            .arg(tz_arg())
+                            .arg(color_arg())
+                            .arg(holidays_arg())
+                    )
+                    .subcommand(
+                        SubCommand::with_name("heatmap")
+                            .about("Print heatmap of events per day of the year.\n\tcargo run -- heatmap --year 2023")
+                            .arg(
+                                Arg::new("year")
+                                .long("year")
+                                .takes_value(true)
+                                .value_name("YYYY")
+                                .required(false)
+                                .help("Year to show. No year: use current year")
+                            )
+                            .args(category_filter_args())
+                            .args(tag_args())
+                            .arg(tz_arg())
+                    )
+                    .subcommand(
+                        SubCommand::with_name("timeline")
+                            .about("Print events on a horizontal timeline spaced by date.\n\tcargo run -- timeline --category microsoft")
+                            .args(category_filter_args())
+                            .args(tag_args())
+                            .arg(tz_arg())
+                    )
+                    .subcommand(
+                        SubCommand::with_name("search")
+                            .about("Search events by description and category, best matches first. Typos are tolerated.\n\tcargo run -- search catalna")
+                            .arg(
+                                Arg::new("terms")
+                                .takes_value(true)
+                                .multiple_values(true)
+                                .required(true)
+                                .value_name("TERM")
+                                .help("Words to search, every word has to match")
+                            )
+                            .arg(
+                                Arg::new(LIMIT_ARG)
+                                .long(LIMIT_ARG)
+                                .takes_value(true)
+                                .value_name("N")
+                                .default_value("10")
+                                .help("Print at most N best matches.")
+                            )
+                            .args(category_filter_args())
+                            .args(tag_args())
+                            .arg(tz_arg())
+                            .arg(color_arg())
+                    )
+                    .subcommand(
+                        SubCommand::with_name("index")
+                            .about("Build search index used by search, kept up to date by add, delete and category.\n\tcargo run -- index")
+                            .arg(
+                                Arg::new("drop")
+                                .long("drop")
+                                .takes_value(false)
+                                .required(false)
+                                .help("Remove the search index, search reads all events then.")
+                            )
+                    )
+                    .subcommand(
+                        SubCommand::with_name("stats")
+                            .about("Print statistics of the events chosen like in list.\n\tcargo run -- stats --category apple")
+                            .args(list_filter_args())
+                            .args(match_args())
+                            .args(tag_args())
+                            .arg(tz_arg())
+                            .arg(
+                                Arg::new("json")
+                                .long("json")
+                                .takes_value(false)
+                                .required(false)
+                                .help("Print statistics as JSON.")
+                            )
+                    )
+                    .subcommand(
+                        SubCommand::with_name("export")
+                            .about("Export events to a file.\n\tcargo run -- export --format svg --out timeline.svg")
+                            .arg(
+                                Arg::new(FORMAT_ARG)
+                                .long(FORMAT_ARG)
+                                .takes_value(true)
+                                .possible_values(["svg", "html", "markdown", "org"])
+                                .required(true)
+                                .help("Export format. svg: timeline with one lane per primary category, html: static site to the --out directory, markdown and org: events by year and month")
+                            )
+                            .arg(
+                                Arg::new(OUT_ARG)
+                                .long(OUT_ARG)
+                                .takes_value(true)
+                                .value_name("PATH")
+                                .required(false)
+                                .help("File to write, or directory for html. No path: print to standard output")
+                            )
+                            .args(category_filter_args())
+                            .args(tag_args())
+                            .arg(tz_arg())
+                    )
+                    .subcommand(
+                        SubCommand::with_name("tags")
+                            .about("Print all used tags with event counts.")
                     )
                     .get_matches();
 
@@ -177,6 +1043,19 @@ fn main() {
     let full_path = current_dir.join(relative_path);
     let path_string = full_path.to_string_lossy().into_owned();
 
+    // optional known categories and aliases are in categories.csv next to the events
+    let registry_path = current_dir.join(Path::new("src").join("utils").join("categories.csv"));
+    let registry = match CategoryRegistry::read(&registry_path.to_string_lossy()) {
+        Ok(registry) => registry,
+        Err(err) => {
+            eprintln!("Error reading categories file: {}", err);
+            return;
+        }
+    };
+
+    // optional search index is in index.csv next to the events, made with the index subcommand
+    let index_path = current_dir.join(Path::new("src").join("utils").join("index.csv")).to_string_lossy().into_owned();
+
     // create event vector
     let orig_events = match read_csv(&path_string) {
         Ok(csv_events) => csv_events,
@@ -193,207 +1072,246 @@ fn main() {
     // match subvommand matches to list, add and delete
     match matches.subcommand() {
         Some(("list", list_matches)) => {
-            // without args present, print all events
-            if !list_matches.args_present() {
-                if let Err(err) = filter_by_date(&orig_events, &mut result_events, "", DateComparison::All) {
-                    eprintln!("Error parsing date: {}", err);
-                    std::process::exit(1);
+            let zone = view_zone(list_matches);
+            let palette = palette(list_matches, &registry);
+            // holidays are virtual events from the first year of the events to this year, so filters choose them too
+            let mut all_events = orig_events.clone();
+            if let Some(holidays) = holiday_calendar(list_matches) {
+                let years: Vec<i32> = orig_events.iter().map(|event| event.date_in(&zone).year()).chain([zone.today().year()]).collect();
+                let start = NaiveDate::from_ymd_opt(years.iter().copied().min().unwrap_or_default(), 1, 1);
+                let end = NaiveDate::from_ymd_opt(years.iter().copied().max().unwrap_or_default(), 12, 31);
+                if let (Some(start), Some(end)) = (start, end) {
+                    all_events.extend(holidays.events_between(start, end));
                 }
             }
+            let mut result_events = choose_list_events(list_matches, &all_events, &registry, &zone);
 
-            // add todays date matches on results
-            if list_matches.is_present(TODAY_ARG) {
-                if let Err(err) = filter_by_date(&orig_events, &mut result_events, "", DateComparison::Today) {
-                    eprintln!("Error parsing date: {}", err);
-                    std::process::exit(1);
-                }
-            }
-            // filtered with both after and before date
-            if list_matches.is_present(BEFORE_DATE_ARG) && list_matches.is_present(AFTER_DATE_ARG) {
-                if let (Some(date1), Some(date2)) = (
-                    list_matches.value_of(BEFORE_DATE_ARG),
-                    list_matches.value_of(AFTER_DATE_ARG),
-                ) {
-                    if validate_date_format(date1) {
-                        if validate_date_format(date2) {
-                            let date_range = format!("{},{}", date1, date2);
-                            if let Err(err) = filter_by_date(&orig_events, &mut result_events, &date_range, DateComparison::BeforeAfter) {
-                                eprintln!("Error parsing date: {}", err);
-                                std::process::exit(1);
-                            }
-                        } else {
-                            eprintln!("Error parsing after-date. Use format YYYY-mm-dd.");
-                            std::process::exit(1);
-                        }
-                    } else {
-                        eprintln!("Error parsing before-date. Use format YYYY-mm-dd.");
+            // sort all events before limiting them, so the limits take for example the latest events
+            let sort_key = SortKey::parse(list_matches.value_of(SORT_ARG).unwrap_or("date")).unwrap_or_else(|err| {
+                eprintln!("{}", err);
+                std::process::exit(1);
+            });
+            let reverse = list_matches.is_present(REVERSE_ARG);
+            sort_events(&mut result_events, sort_key, reverse);
+            let mut result_events = slice_events(
+                result_events,
+                count_arg(list_matches, OFFSET_ARG).unwrap_or(0),
+                count_arg(list_matches, LIMIT_ARG),
+                count_arg(list_matches, TAIL_ARG),
+            );
+
+            // one group of all events without --group-by
+            let groups = match list_matches.value_of(GROUP_BY_ARG) {
+                Some(group_by) => {
+                    let group_by = GroupBy::parse(group_by).unwrap_or_else(|err| {
+                        eprintln!("{}", err);
                         std::process::exit(1);
-                    }
+                    });
+                    group_events(&result_events, group_by, &zone)
                 }
-            // add before given date matches on results
-            } else if let Some(date) = list_matches.value_of(BEFORE_DATE_ARG) {
-                if validate_date_format(date) {
-                    if let Err(err) = filter_by_date(&orig_events, &mut result_events, date, DateComparison::Before) {
-                        eprintln!("Error parsing date: {}", err);
-                        std::process::exit(1);
-                    }
-                } else {
-                    eprintln!("Error parsing date. Use format YYYY-mm-dd.");
-                    std::process::exit(1);
-                }
-            // add after given date matches on results
-            } else if let Some(date) = list_matches.value_of(AFTER_DATE_ARG) {
-                if validate_date_format(date) {
-                    if let Err(err) = filter_by_date(&orig_events, &mut result_events, date, DateComparison::After) {
-                        eprintln!("Error parsing date: {}", err);
-                        std::process::exit(1);
+                None => vec![(String::new(), std::mem::take(&mut result_events))],
+            };
+
+            // print only the counts
+            if list_matches.is_present(COUNT_ARG) {
+                for (name, group) in &groups {
+                    if name.is_empty() {
+                        println!("{}", group.len());
+                    } else {
+                        println!("{}: {}", name, group.len());
                     }
-                } else {
-                    eprintln!("Error parsing date. Use format YYYY-mm-dd.");
-                    std::process::exit(1);
                 }
+                return;
             }
 
-            // add given date matches on results
-            if let Some(date) = list_matches.value_of(DATE_ARG) {
-                if validate_date_format(date) {
-                    if let Err(err) = filter_by_date(&orig_events, &mut result_events, date, DateComparison::Exact) {
-                        eprintln!("Error parsing date: {}", err);
+            // print all results with template, as table or as lines
+            let template = list_matches.value_of(FORMAT_ARG).map(|format| {
+                // named templates are in templates.csv next to the events
+                let templates_path = current_dir.join(Path::new("src").join("utils").join("templates.csv"));
+                let templates = read_templates(&templates_path.to_string_lossy()).unwrap_or_else(|err| {
+                    eprintln!("Error reading templates file: {}", err);
+                    std::process::exit(1);
+                });
+                Template::parse(templates.get(format).map(String::as_str).unwrap_or(format)).unwrap_or_else(|err| {
+                    eprintln!("{}", err);
+                    std::process::exit(1);
+                })
+            });
+            let relative = list_matches.is_present(RELATIVE_ARG);
+            let columns = if list_matches.is_present(TABLE_ARG) || list_matches.is_present(COLUMNS_ARG) {
+                let mut columns = match list_matches.value_of(COLUMNS_ARG) {
+                    Some(columns) => Column::parse_list(columns).unwrap_or_else(|err| {
+                        eprintln!("{}", err);
                         std::process::exit(1);
+                    }),
+                    None => Column::DEFAULT.to_vec(),
+                };
+                if relative && !columns.contains(&Column::Relative) {
+                    columns.insert(1, Column::Relative);
+                }
+                Some(columns)
+            } else {
+                None
+            };
+            // collect lines first, so they can be shown with pager when they dont fit on the screen
+            let today = zone.today();
+            let mut lines = Vec::new();
+            for (index, (name, mut group)) in groups.into_iter().enumerate() {
+                sort_events(&mut group, sort_key, reverse);
+                if !name.is_empty() {
+                    if index > 0 {
+                        lines.push(String::new());
                     }
+                    lines.push(palette.paint(&format!("{} ({})", name, group.len()), Some("1")));
+                }
+                if let Some(template) = &template {
+                    lines.extend(group.iter().map(|event| template.render(event, &zone)));
+                } else if let Some(columns) = &columns {
+                    lines.extend(render_table(&group, columns, terminal_width(), &zone, &palette));
                 } else {
-                    eprintln!("Error parsing date. Use format YYYY-mm-dd.");
-                    std::process::exit(1);
+                    lines.extend(group.iter().map(|event| match relative {
+                        true => format!("{} ({})", format_colored(event, &zone, &palette, today), format_relative(event.date_in(&zone), today)),
+                        false => format_colored(event, &zone, &palette, today),
+                    }));
                 }
-
-            }
-
-            // add given category/categories matches to results
-            if let Some(description) = list_matches.value_of(DESCRIPTION_ARG) {
-                filter_by_string(&orig_events, &mut result_events, description , false, false);
-            }
-
-            // add given category/categories matches to results, depending if excluded or not
-            if let Some(categories) = list_matches.value_of(CATEGORY_ARG) {
-                let exclude = list_matches.is_present("exclude");
-                filter_by_string(&orig_events, &mut result_events, categories , exclude, true);
             }
-
-            // print all results
-            print_events(&mut result_events);
+            print_paged(&lines);
         }
 
         // add given event to the used file
         Some(("add", add_matches)) => {
+            // date arithmetic instead of adding an event
+            if let Some(offset) = add_matches.value_of("offset") {
+                let date = date_arg(add_matches, "day", &ViewZone::Local);
+                let holidays = holiday_calendar(add_matches);
+                let moved = offset_date(date, offset, holidays.as_ref()).unwrap_or_else(|err| {
+                    eprintln!("{}", err);
+                    std::process::exit(1);
+                });
+                println!("{}", moved.format("%Y-%m-%d %A"));
+                return;
+            }
 
-            // only if description arg is given do all
-            if let Some(description_str) = add_matches.value_of(DESCRIPTION_ARG) {
-                // open file for appending, exit program if fail
-                let mut file = match open_file_for_append(&path_string) {
-                    Ok(file) => file,
-                    Err(err) => {
-                        eprintln!("Error opening file: {}", err);
-                        std::process::exit(1);
-                    }
-                };
+            // description is required without the offset
+            let description_str = add_matches.value_of(DESCRIPTION_ARG).unwrap_or_default();
 
-                // use given date, if the date is in correct format ...
-                let event_naive = if let Some(event_date_str) = add_matches.value_of(DATE_ARG) {
-                    // validate and test the date. if either of them fail, exit the program
-                    if validate_date_format(event_date_str) {
-                        match Event::test_date(event_date_str) {
-                            Ok(event_date) => event_date,
-                            Err(err) => {
-                                eprintln!("Error parsing date: {}", err);
-                                std::process::exit(1);
-                            }
+            // open file for appending, exit program if fail
+            let mut file = match open_file_for_append(&path_string) {
+                Ok(file) => file,
+                Err(err) => {
+                    eprintln!("Error opening file: {}", err);
+                    std::process::exit(1);
+                }
+            };
+
+            // use given date, if the date is in correct format ...
+            let event_naive = if let Some(event_date_str) = add_matches.value_of(DATE_ARG) {
+                // validate and test the date. if either of them fail, exit the program
+                if validate_date_format(event_date_str) {
+                    match Event::test_date(event_date_str) {
+                        Ok(event_date) => event_date,
+                        Err(err) => {
+                            eprintln!("Error parsing date: {}", err);
+                            std::process::exit(1);
                         }
-                    } else {
-                        eprint!("Date need to be YYYY-mm-dd");
-                        std::process::exit(1);
                     }
-                // ... else use todays date
                 } else {
-                    chrono::Local::now().naive_local().date()
+                    eprint!("Date need to be YYYY-mm-dd");
+                    std::process::exit(1);
+                }
+            // ... else use todays date
+            } else {
+                chrono::Local::now().naive_local().date()
+            };
+
+            // create category path from possibly given category arg, if no input use empty category
+            let category = add_matches.value_of(CATEGORY_ARG).map(parse_category_arg).unwrap_or_default();
+
+            // resolve aliases and check that category is declared in categories file
+            let category = registry.resolve(&category);
+            if !registry.is_known(&category) {
+                let suggestion = match registry.suggest(&category) {
+                    Some(known) => format!(" Did you mean: {}?", known),
+                    None => String::new(),
                 };
+                if add_matches.is_present("allow-unknown") {
+                    eprintln!("Warning: Unknown category: {}.{}", category.join("/"), suggestion);
+                } else {
+                    eprintln!("Error: Unknown category: {}.{} Use --allow-unknown to add it anyway.", category.join("/"), suggestion);
+                    std::process::exit(1);
+                }
+            }
 
-                // create primary and secondary categories from possibly given category arg
-                let (primary_category_str, secondary_category_str) = match add_matches.value_of(CATEGORY_ARG) {
-                    // use parse_string() to get 2 strings depending on given category input
-                    Some(category) => {
-                        if category.contains('/') {
-                            eprintln!("Cannot use \'/\' in the category.");
-                            std::process::exit(1);
-                        }
-                        let lower_category = category.to_lowercase();
+            // create event from the information
+            let mut new_event = Event::new(
+                event_naive,
+                description_str.to_string(),
+                category
+            );
 
-                        match parse_string(&lower_category, ',') {
-                            Ok((primary,secondary)) => (primary, secondary),
-                            Err(err) => {
-                                eprintln!("{}", err);
-                                std::process::exit(1);
-                            }
-                        }
+            // give event time of day in given or local time zone if time is given
+            if let Some(time_str) = add_matches.value_of(TIME_ARG) {
+                let zone_name = add_matches.value_of(TIMEZONE_ARG).unwrap_or_default();
+                let event_time = match parse_time(time_str).and_then(|time| resolve_time(event_naive, time, zone_name)) {
+                    Ok(event_time) => event_time,
+                    Err(err) => {
+                        eprintln!("Error parsing time: {}", err);
+                        std::process::exit(1);
                     }
-                    // if no input, use empty category strings
-                    None => (String::new(), String::new()),
                 };
+                new_event = new_event.with_time(event_time, zone_name.to_string());
+            }
 
-                // create event from the information
-                let new_event = Event::new(
-                    event_naive,
-                    description_str.to_string(),
-                    primary_category_str,
-                    secondary_category_str
-                );
-
-                // append event to the file if no errors appear
-                if let Err(err) = append_to_csv(&mut file, new_event.format_to_string(StringFormat::Csv)) {
-                    eprintln!("Error appending to CSV file: {}", err);
-                    std::process::exit(1);
-                }
-            // if no description arg, stop running
-            } else {
-                eprintln!("Error: Can't add event without description argument.");
+            // give event tags if given
+            if let Some(tags) = add_matches.values_of(TAG_ARG) {
+                new_event = new_event.with_tags(parse_tags(&tags.collect::<Vec<_>>().join(",")));
+            }
+
+            // append event to the file if no errors appear
+            if let Err(err) = append_to_csv(&mut file, &new_event) {
+                eprintln!("Error appending to CSV file: {}", err);
                 std::process::exit(1);
             }
+            update_index(&index_path, |index| index.add_event(&new_event));
         }
         // delete filtered dates if not dry-run
         Some(("delete", delete_matches)) => {
+            let zone = view_zone(delete_matches);
+            let palette = palette(delete_matches, &registry);
             // check for dry-run
             let dry_run = delete_matches.is_present("dry-run");
-            // no args given stop running
-            if !delete_matches.args_present() {
-                eprintln!("Error: Add filters. Available filters: all, description, category, date, after-date, before-date, today, dry-run. More info from --help");
+            // no filter args given stop running, args with default values are always present
+            if !DELETE_FILTER_ARGS.iter().any(|arg| delete_matches.is_present(arg)) {
+                eprintln!("Error: Add filters. Available filters: all, description, category, date, after-date, before-date, today, tag, any-tag, all-tags, no-tag, dry-run. More info from --help");
                 std::process::exit(1);
             }
 
             // filter all to delete
             if delete_matches.is_present("all") {
-                if let Err(err) = filter_by_date(&orig_events, &mut result_events, "", DateComparison::All) {
+                if let Err(err) = filter_by_date_in(&orig_events, &mut result_events, "", DateComparison::All, &zone) {
                     eprintln!("Error parsing date: {}", err);
                     std::process::exit(1);
                 }
             // if rest of the accepted args are present
             } else if delete_matches.is_present(TODAY_ARG) {
                 // filter to delete with today
-                if let Err(err) = filter_by_date(&orig_events, &mut result_events, "", DateComparison::Today) {
+                if let Err(err) = filter_by_date_in(&orig_events, &mut result_events, "", DateComparison::Today, &zone) {
                     eprintln!("Error parsing date: {}", err);
                     std::process::exit(1);
                 }
-            } else if delete_matches.is_present(DESCRIPTION_ARG) || delete_matches.is_present(CATEGORY_ARG) || delete_matches.is_present(DATE_ARG) || delete_matches.is_present(AFTER_DATE_ARG) || delete_matches.is_present(BEFORE_DATE_ARG)|| delete_matches.is_present(DATE_ARG) {
+            } else if delete_matches.is_present(DESCRIPTION_ARG) || delete_matches.is_present(CATEGORY_ARG) || delete_matches.is_present(DATE_ARG) || delete_matches.is_present(AFTER_DATE_ARG) || delete_matches.is_present(BEFORE_DATE_ARG)|| delete_matches.is_present(DATE_ARG) || TAG_FILTER_ARGS.iter().any(|arg| delete_matches.is_present(arg)) {
                 // filter to delete with description
                 if let Some(description_str) = delete_matches.value_of(DESCRIPTION_ARG) {
-                    filter_by_string(&orig_events, &mut result_events, description_str, false, false);
+                    filter_strings(delete_matches, &orig_events, &mut result_events, description_str, false, false, &registry);
                 }
                 // filter to delete with category
                 if let Some(category_str) = delete_matches.value_of(CATEGORY_ARG) {
-                    filter_by_string(&orig_events, &mut result_events, category_str, false, true)
+                    filter_strings(delete_matches, &orig_events, &mut result_events, category_str, false, true, &registry)
                 }
                 // filter to delete with date while validating the given input
                 if let Some(date) = delete_matches.value_of(DATE_ARG) {
                     if validate_date_format(date) {
-                        if let Err(err) = filter_by_date(&orig_events, &mut result_events, date, DateComparison::Exact) {
+                        if let Err(err) = filter_by_date_in(&orig_events, &mut result_events, date, DateComparison::Exact, &zone) {
                             eprintln!("Error parsing date: {}", err);
                             std::process::exit(1);
                         }
@@ -411,7 +1329,7 @@ fn main() {
                         if validate_date_format(date1) {
                             if validate_date_format(date2) {
                                 let date_range = format!("{},{}", date1, date2);
-                                if let Err(err) = filter_by_date(&orig_events, &mut result_events, &date_range, DateComparison::BeforeAfter) {
+                                if let Err(err) = filter_by_date_in(&orig_events, &mut result_events, &date_range, DateComparison::BeforeAfter, &zone) {
                                     eprintln!("Error parsing date: {}", err);
                                     std::process::exit(1);
                                 }
@@ -427,7 +1345,7 @@ fn main() {
                 // add before given date matches on results
                 } else if let Some(date) = delete_matches.value_of(BEFORE_DATE_ARG) {
                     if validate_date_format(date) {
-                        if let Err(err) = filter_by_date(&orig_events, &mut result_events, date, DateComparison::Before) {
+                        if let Err(err) = filter_by_date_in(&orig_events, &mut result_events, date, DateComparison::Before, &zone) {
                             eprintln!("Error parsing date: {}", err);
                             std::process::exit(1);
                         }
@@ -438,7 +1356,7 @@ fn main() {
                 // add after given date matches on results
                 } else if let Some(date) = delete_matches.value_of(AFTER_DATE_ARG) {
                     if validate_date_format(date) {
-                        if let Err(err) = filter_by_date(&orig_events, &mut result_events, date, DateComparison::After) {
+                        if let Err(err) = filter_by_date_in(&orig_events, &mut result_events, date, DateComparison::After, &zone) {
                             eprintln!("Error parsing date: {}", err);
                             std::process::exit(1);
                         }
@@ -447,12 +1365,14 @@ fn main() {
                         std::process::exit(1);
                     }
                 }
+                // filter to delete with tags
+                filter_tags(delete_matches, &orig_events, &mut result_events, choosing_args_present(delete_matches, DELETE_FILTER_ARGS));
             }
             // rewrite the file without filtered events or just print them on when dry-run
             match dry_run {
                 true => {
                     println!("Following events are filtered for deleting:");
-                    print_events(&mut result_events)
+                    print_events_colored(&mut result_events, &zone, &palette)
                 },
                 false => {
                     // Perform actual deletion of events
@@ -462,17 +1382,442 @@ fn main() {
                             eprintln!("Error deleting events: {}", err);
                             std::process::exit(1);
                         });
+                    let remaining_events: Vec<Event> = orig_events.iter().filter(|event| !result_events.contains(event)).cloned().collect();
+                    update_index(&index_path, |index| index.remove_events(&result_events, &remaining_events));
+                }
+            }
+        }
+        // print time from today to future events or from past events to today
+        Some((command @ ("until" | "since"), span_matches)) => {
+            let zone = view_zone(span_matches);
+            let palette = palette(span_matches, &registry);
+            let today = zone.today();
+            let until = command == "until";
+            let business = span_matches.is_present(BUSINESS_ARG);
+            let holidays = holiday_calendar(span_matches);
+            let selector = span_matches.value_of("selector").unwrap_or_default();
+            let format_span_to = |date: NaiveDate| match (date == today, until) {
+                (true, _) => "today".to_string(),
+                (false, true) => format!("in {}", format_span(today, date, business, holidays.as_ref())),
+                (false, false) => format!("{} ago", format_span(date, today, business, holidays.as_ref())),
+            };
+
+            // date selector is counted as it is
+            if validate_date_format(selector) {
+                let date = NaiveDate::parse_from_str(selector, "%Y-%m-%d").unwrap_or_else(|err| {
+                    eprintln!("Error parsing date: {}", err);
+                    std::process::exit(1);
+                });
+                println!("{}: {}", selector, format_span_to(date));
+                return;
+            }
+            let mut events: Vec<Event> = select_events(span_matches, &orig_events, selector, &registry)
+                .into_iter()
+                .filter(|event| if until { event.date_in(&zone) >= today } else { event.date_in(&zone) <= today })
+                .collect();
+            if events.is_empty() {
+                println!("No {} events matching: {}", if until { "upcoming" } else { "past" }, selector);
+                return;
+            }
+            events.sort();
+            for event in &events {
+                println!("{}: {}", format_colored(event, &zone, &palette, today), format_span_to(event.date_in(&zone)));
+            }
+        }
+        // print coming events and anniversaries with time until them
+        Some(("upcoming", upcoming_matches)) => {
+            let zone = view_zone(upcoming_matches);
+            let palette = palette(upcoming_matches, &registry);
+            let today = zone.today();
+            let occurrences = choose_upcoming(upcoming_matches, &orig_events, &registry, &zone);
+            if occurrences.is_empty() {
+                println!("No upcoming events.");
+            }
+            for occurrence in &occurrences {
+                println!("{}, {}", format_occurrence(occurrence, &zone, &palette, today), format_relative(occurrence.date, today));
+            }
+        }
+        // print coming events and anniversaries under day headings
+        Some(("agenda", agenda_matches)) => {
+            let zone = view_zone(agenda_matches);
+            let palette = palette(agenda_matches, &registry);
+            let today = zone.today();
+            let sections = group_agenda(choose_upcoming(agenda_matches, &orig_events, &registry, &zone), today);
+            if sections.is_empty() {
+                println!("Nothing coming up.");
+            }
+            for (index, (name, occurrences)) in sections.iter().enumerate() {
+                if index > 0 {
+                    println!();
+                }
+                println!("{}", palette.paint(name, Some("1")));
+                for occurrence in occurrences {
+                    println!("  {}", format_occurrence(occurrence, &zone, &palette, today));
+                }
+            }
+        }
+        // date arithmetic without events
+        Some(("diff", diff_matches)) => {
+            let zone = view_zone(diff_matches);
+            let from = date_arg(diff_matches, "from", &zone);
+            let to = date_arg(diff_matches, "to", &zone);
+            for line in format_diff(from, to, holiday_calendar(diff_matches).as_ref()) {
+                println!("{}", line);
+            }
+        }
+        Some(("weekday", weekday_matches)) => {
+            let date = date_arg(weekday_matches, "day", &view_zone(weekday_matches));
+            println!("{}", date.format("%A"));
+        }
+        Some(("week", week_matches)) => {
+            let date = date_arg(week_matches, "day", &view_zone(week_matches));
+            let week = date.iso_week();
+            println!("{}-W{:02}", week.year(), week.week());
+        }
+        // find duplicate groups and remove all but one event of each
+        Some(("dedupe", dedupe_matches)) => {
+            let zone = view_zone(dedupe_matches);
+            let palette = palette(dedupe_matches, &registry);
+            let today = zone.today();
+            let min_similarity = match dedupe_matches.value_of("similarity").unwrap_or("0.8").parse::<f64>() {
+                Ok(value) if (0.0..=1.0).contains(&value) => value,
+                _ => {
+                    eprintln!("Error parsing --similarity. Give a number from 0 to 1.");
+                    std::process::exit(1);
+                }
+            };
+            let within_days = count_arg(dedupe_matches, "within").unwrap_or(0) as i64;
+            let keep = dedupe_matches.value_of("keep").map(|keep| {
+                Keep::parse(keep).unwrap_or_else(|err| {
+                    eprintln!("{}", err);
+                    std::process::exit(1);
+                })
+            });
+            let dry_run = dedupe_matches.is_present("dry-run");
+
+            let groups = find_duplicates(&orig_events, min_similarity, within_days, &zone);
+            if groups.is_empty() {
+                println!("No duplicates found.");
+                return;
+            }
+            // without --keep ask only if there is someone to answer
+            let interactive = keep.is_none() && !dry_run && std::io::stdin().is_terminal();
+            let mut removed: BTreeSet<usize> = BTreeSet::new();
+            for (number, group) in groups.iter().enumerate() {
+                println!("Group {} of {}:", number + 1, groups.len());
+                let survivor = keep.map(|keep| keep.survivor(group, &orig_events));
+                for (position, index) in group.iter().enumerate() {
+                    let mark = match survivor {
+                        Some(survivor) if survivor == position => "keep",
+                        Some(_) => "remove",
+                        None => "",
+                    };
+                    let line = format!("  {}) {} {}", position + 1, format_colored(&orig_events[*index], &zone, &palette, today), mark);
+                    println!("{}", line.trim_end());
+                }
+                let survivor = if interactive { ask_survivor(group.len()) } else { survivor };
+                if let Some(survivor) = survivor {
+                    removed.extend(group.iter().enumerate().filter(|(position, _)| *position != survivor).map(|(_, index)| *index));
+                }
+            }
+            if keep.is_none() && !interactive {
+                println!("Use --keep first, last or longest to remove duplicates.");
+                return;
+            }
+            let details = format!("{} duplicate events in {} groups", removed.len(), groups.len());
+            if dry_run {
+                println!("Dry run, nothing changed: {}", details);
+                return;
+            }
+            if removed.is_empty() {
+                println!("Nothing removed.");
+                return;
+            }
+
+            // rewrite the file without removed events, same events may be on many rows so rows are removed by position
+            let removed_events: Vec<Event> = removed.iter().map(|index| orig_events[*index].clone()).collect();
+            let remaining_events: Vec<Event> = orig_events.iter().enumerate().filter(|(index, _)| !removed.contains(index)).map(|(_, event)| event.clone()).collect();
+            if let Err(err) = write_csv(&path_string, &remaining_events) {
+                eprintln!("Error writing CSV file: {}", err);
+                std::process::exit(1);
+            }
+            update_index(&index_path, |index| index.remove_events(&removed_events, &remaining_events));
+            let history_path = current_dir.join(Path::new("src").join("utils").join("history.csv"));
+            if let Err(err) = append_history(&history_path.to_string_lossy(), "dedupe", &details) {
+                eprintln!("Error writing history: {}", err);
+                std::process::exit(1);
+            }
+            println!("Removed: {}", details);
+        }
+        // print categories as a list or tree with counts
+        Some(("categories", categories_matches)) => {
+            if categories_matches.is_present("tree") {
+                for line in format_category_tree(&build_category_tree(&orig_events)) {
+                    println!("{}", line);
+                }
+            } else {
+                for (category, count) in count_categories(&orig_events) {
+                    // show description of declared categories
+                    let path: Vec<String> = category.split('/').map(String::from).collect();
+                    match registry.info(&path).filter(|info| !info.description.is_empty()) {
+                        Some(info) => println!("{}: {} - {}", category, count, info.description),
+                        None => println!("{}: {}", category, count),
+                    }
                 }
             }
         }
+        // rename or merge categories by rewriting all events in the file
+        Some(("category", category_matches)) => {
+            let (action, action_matches, mut categories) = match category_matches.subcommand() {
+                Some(("rename", rename_matches)) => {
+                    let categories = vec![rename_matches.value_of("old").unwrap_or_default(), rename_matches.value_of("new").unwrap_or_default()];
+                    ("rename", rename_matches, categories)
+                }
+                Some(("merge", merge_matches)) => {
+                    let categories = merge_matches.values_of("categories").map(|values| values.collect()).unwrap_or_default();
+                    ("merge", merge_matches, categories)
+                }
+                _ => {
+                    eprintln!("Error: Use category rename or category merge. More info from --help");
+                    std::process::exit(1);
+                }
+            };
+
+            // last category is the target, rest are the sources. Aliases are resolved in both
+            let target = registry.resolve(&parse_category_arg(categories.pop().unwrap_or_default()));
+            let sources: Vec<Vec<String>> = categories.into_iter().map(|category| registry.resolve(&parse_category_arg(category))).collect();
+            if target.is_empty() || sources.iter().any(|source| source.is_empty()) {
+                eprintln!("Error: Categories cannot be empty.");
+                std::process::exit(1);
+            }
+            if !registry.is_known(&target) {
+                eprintln!("Warning: Unknown category: {}.", target.join("/"));
+            }
+
+            // show preview of the changes
+            let changes = move_categories(&orig_events, &sources, &target);
+            for (old, new) in &changes {
+                println!("{} -> {}", old.format_to_string(StringFormat::Print), new.category().join("/"));
+            }
+            let source_names: Vec<String> = sources.iter().map(|source| source.join("/")).collect();
+            let details = format!("{} -> {} ({} events)", source_names.join(", "), target.join("/"), changes.len());
+            if action_matches.is_present("dry-run") {
+                println!("Dry run, nothing changed: {}", details);
+                return;
+            }
+            if changes.is_empty() {
+                println!("No events in category: {}", source_names.join(", "));
+                return;
+            }
+
+            // rewrite the file with changed events and save the change in history
+            let new_events: Vec<Event> = orig_events
+                .iter()
+                .map(|event| changes.iter().find(|(old, _)| old == event).map(|(_, new)| new.clone()).unwrap_or_else(|| event.clone()))
+                .collect();
+            if let Err(err) = write_csv(&path_string, &new_events) {
+                eprintln!("Error writing CSV file: {}", err);
+                std::process::exit(1);
+            }
+            update_index(&index_path, |index| {
+                let old_events: Vec<Event> = changes.iter().map(|(old, _)| old.clone()).collect();
+                index.remove_events(&old_events, &new_events);
+                for (_, new) in &changes {
+                    index.add_event(new);
+                }
+            });
+            let history_path = current_dir.join(Path::new("src").join("utils").join("history.csv"));
+            if let Err(err) = append_history(&history_path.to_string_lossy(), &format!("category {}", action), &details) {
+                eprintln!("Error writing history: {}", err);
+                std::process::exit(1);
+            }
+            println!("Changed: {}", details);
+        }
+        // print month grid(s) and the events in them
+        Some(("cal", cal_matches)) => {
+            let zone = view_zone(cal_matches);
+            let palette = palette(cal_matches, &registry);
+            let today = zone.today();
+            let month = match cal_matches.value_of("month") {
+                Some(month_str) => parse_month(month_str).unwrap_or_else(|err| {
+                    eprintln!("{}", err);
+                    std::process::exit(1);
+                }),
+                None => today.with_day(1).unwrap_or(today),
+            };
+            let months = if cal_matches.is_present("three") {
+                vec![shift_month(month, -1), month, shift_month(month, 1)]
+            } else {
+                vec![month]
+            };
+
+            // category and tag filters first, shown months are then chosen with filter_by_date
+            let mut chosen_events = choose_by_category_and_tags(cal_matches, &orig_events, &registry);
+            if let Some(holidays) = holiday_calendar(cal_matches) {
+                chosen_events.extend(holidays.events_between(months[0], last_day_of_month(months[months.len() - 1])));
+            }
+            let before = last_day_of_month(months[months.len() - 1]).succ_opt().unwrap_or(today);
+            let after = months[0].pred_opt().unwrap_or(today);
+            let date_range = format!("{},{}", before.format("%Y-%m-%d"), after.format("%Y-%m-%d"));
+            if let Err(err) = filter_by_date_in(&chosen_events, &mut result_events, &date_range, DateComparison::BeforeAfter, &zone) {
+                eprintln!("Error parsing date: {}", err);
+                std::process::exit(1);
+            }
+
+            let marked: BTreeSet<_> = result_events.iter().map(|event| event.date_in(&zone)).collect();
+            let grids: Vec<Vec<String>> = months.iter().map(|first_day| month_grid(*first_day, &marked, today, &palette)).collect();
+            for line in join_months(&grids) {
+                println!("{}", line);
+            }
+            if !result_events.is_empty() {
+                println!();
+                print_events_colored(&mut result_events, &zone, &palette);
+            }
+        }
+        // print heatmap of the year
+        Some(("heatmap", heatmap_matches)) => {
+            let zone = view_zone(heatmap_matches);
+            let year = match heatmap_matches.value_of("year") {
+                Some(year_str) => year_str.parse().unwrap_or_else(|_| {
+                    eprintln!("Error parsing year. Use format YYYY.");
+                    std::process::exit(1);
+                }),
+                None => zone.today().year(),
+            };
+            let chosen_events = choose_by_category_and_tags(heatmap_matches, &orig_events, &registry);
+            for line in heatmap(&chosen_events, year, &zone) {
+                println!("{}", line);
+            }
+        }
+        // print timeline of the events fitting the terminal
+        Some(("timeline", timeline_matches)) => {
+            let zone = view_zone(timeline_matches);
+            let chosen_events = choose_by_category_and_tags(timeline_matches, &orig_events, &registry);
+            // no terminal width when printing to file, use common terminal width then
+            let width = match terminal_width() {
+                usize::MAX => 80,
+                width => width,
+            };
+            for line in timeline(&chosen_events, width, &zone) {
+                println!("{}", line);
+            }
+        }
+        // print events best matching the search terms
+        Some(("search", search_matches)) => {
+            let zone = view_zone(search_matches);
+            let palette = palette(search_matches, &registry);
+            let chosen_events = choose_by_category_and_tags(search_matches, &orig_events, &registry);
+            let query = search_matches.values_of("terms").map(|terms| terms.collect::<Vec<_>>().join(" ")).unwrap_or_default();
+            // with the index only events having the words are ranked
+            let chosen_events = match SearchIndex::read(&index_path) {
+                Ok(Some(index)) => {
+                    let candidates = index.candidates(&query);
+                    chosen_events.into_iter().filter(|event| candidates.contains(&event.id())).collect()
+                }
+                Ok(None) => chosen_events,
+                Err(err) => {
+                    eprintln!("Error reading search index: {}", err);
+                    std::process::exit(1);
+                }
+            };
+            let hits = search_events(&chosen_events, &query);
+            if hits.is_empty() {
+                println!("No events found for: {}", query);
+            }
+            for hit in hits.iter().take(count_arg(search_matches, LIMIT_ARG).unwrap_or(usize::MAX)) {
+                println!("{}", format_hit(hit, &zone, &palette));
+            }
+        }
+        // build or remove the search index
+        Some(("index", index_matches)) => {
+            if index_matches.is_present("drop") {
+                if Path::new(&index_path).exists() {
+                    if let Err(err) = std::fs::remove_file(&index_path) {
+                        eprintln!("Error removing search index: {}", err);
+                        std::process::exit(1);
+                    }
+                }
+                println!("Search index removed.");
+                return;
+            }
+            if let Err(err) = SearchIndex::build(&orig_events).write(&index_path) {
+                eprintln!("Error writing search index: {}", err);
+                std::process::exit(1);
+            }
+            println!("Indexed {} events.", orig_events.len());
+        }
+        // print statistics of the events list would show
+        Some(("stats", stats_matches)) => {
+            let zone = view_zone(stats_matches);
+            let chosen_events = choose_list_events(stats_matches, &orig_events, &registry, &zone);
+            let stats = Stats::new(&chosen_events, &zone);
+            if stats_matches.is_present("json") {
+                println!("{}", stats_json(&stats, &zone));
+            } else {
+                for line in render_stats(&stats, &zone) {
+                    println!("{}", line);
+                }
+            }
+        }
+        // export chosen events to a file or standard output
+        Some(("export", export_matches)) => {
+            let zone = view_zone(export_matches);
+            let chosen_events = choose_by_category_and_tags(export_matches, &orig_events, &registry);
+            // html is a site of several pages written to the out directory
+            if export_matches.value_of(FORMAT_ARG) == Some("html") {
+                let Some(out_dir) = export_matches.value_of(OUT_ARG) else {
+                    eprintln!("Error: html export needs --out directory");
+                    std::process::exit(1);
+                };
+                if let Err(err) = std::fs::create_dir_all(out_dir) {
+                    eprintln!("Error creating {}: {}", out_dir, err);
+                    std::process::exit(1);
+                }
+                let pages = render_site(&chosen_events, &zone);
+                for (file_name, content) in &pages {
+                    let page_path = Path::new(out_dir).join(file_name);
+                    if let Err(err) = std::fs::write(&page_path, content) {
+                        eprintln!("Error writing {}: {}", page_path.display(), err);
+                        std::process::exit(1);
+                    }
+                }
+                println!("Exported {} events to {} pages in {}", chosen_events.len(), pages.len(), out_dir);
+                return;
+            }
+            let output = match export_matches.value_of(FORMAT_ARG) {
+                Some("svg") => render_svg(&chosen_events, &zone),
+                Some("markdown") => render_markdown(&chosen_events, &zone),
+                Some("org") => render_org(&chosen_events, &zone),
+                _ => {
+                    eprintln!("Error: Unknown export format. Use svg, html, markdown or org");
+                    std::process::exit(1);
+                }
+            };
+            match export_matches.value_of(OUT_ARG) {
+                Some(out_path) => {
+                    if let Err(err) = std::fs::write(out_path, output) {
+                        eprintln!("Error writing {}: {}", out_path, err);
+                        std::process::exit(1);
+                    }
+                    println!("Exported {} events to {}", chosen_events.len(), out_path);
+                }
+                None => print!("{}", output),
+            }
+        }
+        // print tags with counts
+        Some(("tags", _)) => {
+            for (tag, count) in count_tags(&orig_events) {
+                println!("#{}: {}", tag, count);
+            }
+        }
         // if subcommand is given but coded in, give error
         Some((command, _)) => {
-            eprintln!("Error: Not accepted subcommand: {}. Use: list, add or delete", command);
+            eprintln!("Error: Not accepted subcommand: {}. Use: list, add, delete, categories, category, cal, heatmap, timeline, search, index, stats, export, dedupe, until, since, upcoming, agenda, diff, weekday, week or tags", command);
             std::process::exit(1);
         }
         // if no subcommand is given, give error
         None => {
-           eprintln!("Error: No provided subcommand.  Use: list, add or delete");
+           eprintln!("Error: No provided subcommand.  Use: list, add, delete, categories, category, cal, heatmap, timeline, search, index, stats, export, dedupe, until, since, upcoming, agenda, diff, weekday, week or tags");
            std::process::exit(1);
         }
     }
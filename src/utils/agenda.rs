@@ -0,0 +1,124 @@
+use chrono::{Datelike, NaiveDate};
+use crate::utils::all_utils::Event;
+use crate::utils::timezone::ViewZone;
+
+// tags of events that repeat every year on their date, like birthdays
+pub const YEARLY_TAGS: [&str; 2] = ["yearly", "anniversary"];
+
+// event happening on a date, years is set when the date is an anniversary of the event
+#[derive(Debug, Clone, PartialEq)]
+pub struct Occurrence {
+    pub date: NaiveDate,
+    pub event: Event,
+    pub years: Option<u32>,
+}
+
+impl Occurrence {
+    // event moved to the date of the occurrence, for printing
+    pub fn shown_event(&self) -> Event {
+        if self.years.is_none() {
+            return self.event.clone();
+        }
+        Event::new(self.date, self.event.description().to_string(), self.event.category().to_vec()).with_tags(self.event.tags().to_vec())
+    }
+}
+
+// true if the event is tagged to repeat every year
+pub fn is_yearly(event: &Event) -> bool {
+    event.tags().iter().any(|tag| YEARLY_TAGS.contains(&tag.as_str()))
+}
+
+// date of the anniversary in given year, february 29th is on 28th in other years
+fn anniversary_in(date: NaiveDate, year: i32) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(year, date.month(), date.day()).or_else(|| NaiveDate::from_ymd_opt(year, date.month(), date.day() - 1))
+}
+
+// events and anniversaries from today to end, both included, in date order. Yearly tagged events
+// have anniversaries, all events if all_anniversaries is set
+pub fn upcoming(events: &[Event], today: NaiveDate, end: NaiveDate, all_anniversaries: bool, zone: &ViewZone) -> Vec<Occurrence> {
+    let mut occurrences = Vec::new();
+    for event in events {
+        let date = event.date_in(zone);
+        if date >= today && date <= end {
+            occurrences.push(Occurrence { date, event: event.clone(), years: None });
+        }
+        if !(all_anniversaries || is_yearly(event)) {
+            continue;
+        }
+        // anniversaries are projected to this year and the following ones inside the range
+        for year in today.year().max(date.year() + 1)..=end.year() {
+            if let Some(anniversary) = anniversary_in(date, year).filter(|anniversary| *anniversary >= today && *anniversary <= end) {
+                occurrences.push(Occurrence { date: anniversary, event: event.clone(), years: Some((year - date.year()) as u32) });
+            }
+        }
+    }
+    occurrences.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.event.cmp(&b.event)));
+    occurrences
+}
+
+// agenda heading of the date: today, tomorrow, rest of this week until sunday, or later
+pub fn agenda_section(date: NaiveDate, today: NaiveDate) -> &'static str {
+    let days = (date - today).num_days();
+    let days_left_in_week = 6 - i64::from(today.weekday().num_days_from_monday());
+    match days {
+        ..=0 => "Today",
+        1 => "Tomorrow",
+        days if days <= days_left_in_week => "This week",
+        _ => "Later",
+    }
+}
+
+// occurrences under agenda headings in time order, empty headings are left out
+pub fn group_agenda(occurrences: Vec<Occurrence>, today: NaiveDate) -> Vec<(&'static str, Vec<Occurrence>)> {
+    let mut sections: Vec<(&'static str, Vec<Occurrence>)> = Vec::new();
+    for occurrence in occurrences {
+        let section = agenda_section(occurrence.date, today);
+        match sections.last_mut() {
+            Some((name, group)) if *name == section => group.push(occurrence),
+            _ => sections.push((section, vec![occurrence])),
+        }
+    }
+    sections
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("Valid date")
+    }
+
+    fn create_test_events() -> Vec<Event> {
+        vec![
+            Event::new(date(2023, 11, 16), "Meetup".to_string(), vec!["work".to_string()]),
+            Event::new(date(2020, 11, 14), "Wedding".to_string(), vec![]).with_tags(vec!["anniversary".to_string()]),
+            Event::new(date(2020, 2, 29), "Leap birthday".to_string(), vec![]).with_tags(vec!["yearly".to_string()]),
+            Event::new(date(2023, 11, 10), ".NET 8 released".to_string(), vec!["microsoft".to_string()]),
+        ]
+    }
+
+    #[test]
+    fn test_upcoming_projects_anniversaries() {
+        // tuesday
+        let today = date(2023, 11, 14);
+        let occurrences = upcoming(&create_test_events(), today, date(2024, 3, 1), false, &ViewZone::Local);
+        let found: Vec<(NaiveDate, &str, Option<u32>)> = occurrences.iter().map(|o| (o.date, o.event.description(), o.years)).collect();
+        assert_eq!(found, vec![(date(2023, 11, 14), "Wedding", Some(3)), (date(2023, 11, 16), "Meetup", None), (date(2024, 2, 29), "Leap birthday", Some(4))]);
+        assert_eq!(occurrences[0].shown_event().date(), today);
+        // every event has anniversaries when asked
+        assert_eq!(upcoming(&create_test_events(), today, date(2024, 11, 14), true, &ViewZone::Local).len(), 5);
+    }
+
+    #[test]
+    fn test_agenda_sections() {
+        let today = date(2023, 11, 14);
+        assert_eq!(agenda_section(today, today), "Today");
+        assert_eq!(agenda_section(date(2023, 11, 15), today), "Tomorrow");
+        assert_eq!(agenda_section(date(2023, 11, 19), today), "This week");
+        assert_eq!(agenda_section(date(2023, 11, 20), today), "Later");
+        let occurrences = upcoming(&create_test_events(), today, date(2023, 12, 31), false, &ViewZone::Local);
+        let sections: Vec<&str> = group_agenda(occurrences, today).into_iter().map(|(name, _)| name).collect();
+        assert_eq!(sections, vec!["Today", "This week"]);
+    }
+}
@@ -1,28 +1,112 @@
 use std::fs::{File, OpenOptions};
 use std::error::Error as StdError;
-use std::io::{self, Write};
-use csv::{Error, WriterBuilder};
-use chrono::{NaiveDate, ParseError};
+use std::io;
+use csv::{Error, ReaderBuilder, WriterBuilder};
+use chrono::{DateTime, FixedOffset, NaiveDate, ParseError, SecondsFormat};
 use regex::Regex;
+use crate::utils::matcher::{MatchMode, MatchOptions, TextMatcher};
+use crate::utils::registry::CategoryRegistry;
+use crate::utils::tags::parse_tags;
+use crate::utils::timezone::{parse_zone, ViewZone};
 
 // open cloning, equal_to comparison and ordering
 // note: uses date compare&ordering automaticly
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Event {
     date: NaiveDate,
+    time: Option<DateTime<FixedOffset>>,
     description: String,
-    primary_category: String,
-    secondary_category: String,
+    // category path from the top, for example ["vendor", "apple", "macos"]
+    category: Vec<String>,
+    // IANA zone name of the time, empty if time has only offset
+    timezone: String,
+    // flat tags without '#', sorted
+    tags: Vec<String>,
 }
 
 impl Event {
     // create new Event, "constructor"
-    pub fn new(date: NaiveDate, description: String, primary_category: String, secondary_category: String) -> Self {
+    pub fn new(date: NaiveDate, description: String, category: Vec<String>) -> Self {
         Event {
             date,
+            time: None,
             description,
-            primary_category,
-            secondary_category,
+            category,
+            timezone: String::new(),
+            tags: Vec::new(),
+        }
+    }
+
+    // give event new category path
+    pub fn with_category(mut self, category: Vec<String>) -> Self {
+        self.category = category;
+        self
+    }
+
+    // give event tags, expects tags parsed with parse_tags()
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        self.tags = tags;
+        self
+    }
+
+    // date of the event in its own time zone
+    pub fn date(&self) -> NaiveDate {
+        self.date
+    }
+
+    // time of the event if it has one
+    pub fn time(&self) -> Option<&DateTime<FixedOffset>> {
+        self.time.as_ref()
+    }
+
+    // IANA zone name of the time, empty if not known
+    pub fn timezone(&self) -> &str {
+        &self.timezone
+    }
+
+    // short stable id from date, description and category, used to refer to single events
+    pub fn id(&self) -> String {
+        format!("{:08x}", stable_hash(&format!("{}|{}|{}", self.date, self.description, self.category.join("/"))))
+    }
+
+    // description of the event
+    pub fn description(&self) -> &str {
+        &self.description
+    }
+
+    // tags of the event without '#'
+    pub fn tags(&self) -> &[String] {
+        &self.tags
+    }
+
+    // category path of the event, empty if no category
+    pub fn category(&self) -> &[String] {
+        &self.category
+    }
+
+    // first level of the category path or empty string
+    pub fn primary_category(&self) -> &str {
+        self.category.first().map(String::as_str).unwrap_or_default()
+    }
+
+    // second level of the category path or empty string
+    pub fn secondary_category(&self) -> &str {
+        self.category.get(1).map(String::as_str).unwrap_or_default()
+    }
+
+    // give event a time of day, date is moved to the date of the time in its own offset
+    pub fn with_time(mut self, time: DateTime<FixedOffset>, timezone: String) -> Self {
+        self.date = time.date_naive();
+        self.time = Some(time);
+        self.timezone = timezone;
+        self
+    }
+
+    // date of the event as seen in the given zone, events without time stay on their date
+    pub fn date_in(&self, zone: &ViewZone) -> NaiveDate {
+        match &self.time {
+            Some(time) => zone.date_of(time),
+            None => self.date,
         }
     }
 
@@ -32,40 +116,77 @@ impl Event {
     }
 
     // format category in spesific string for csv and printing
-    fn format_category(&self, format:StringFormat) -> String {
+    pub(crate) fn format_category(&self, format:StringFormat) -> String {
         match format {
-            StringFormat::Print => {
-                match (self.primary_category.is_empty(), self.secondary_category.is_empty()) {
-                    (true, true) => "/".to_string(),
-                    (false, true) => self.primary_category.clone(),
-                    _ => format!("{}/{}", self.primary_category, self.secondary_category),
-                }
-            }
-            StringFormat::Csv => {
-                match (self.primary_category.is_empty(), self.secondary_category.is_empty()) {
-                    (true, true) => String::new(),
-                    (false, true) => self.primary_category.clone(),
-                    _ => format!("{}/{}", self.primary_category, self.secondary_category),
-                }
-            }
+            StringFormat::Print if self.category.is_empty() => "/".to_string(),
+            _ => self.category.join("/"),
+        }
+    }
+
+    // format tags for csv as "release lts" and for printing as "#release #lts"
+    pub(crate) fn format_tags(&self, format: &StringFormat) -> String {
+        match format {
+            StringFormat::Print => self.tags.iter().map(|tag| format!("#{}", tag)).collect::<Vec<_>>().join(" "),
+            StringFormat::Csv => self.tags.join(" "),
         }
     }
 
+    // parse csv date field, either YYYY-mm-dd or RFC 3339 time
+    pub fn parse_date_field(date_str: &str) -> Result<(NaiveDate, Option<DateTime<FixedOffset>>), ParseError> {
+        if validate_date_format(date_str) {
+            return Ok((Event::test_date(date_str)?, None));
+        }
+        let time = DateTime::parse_from_rfc3339(date_str)?;
+        Ok((time.date_naive(), Some(time)))
+    }
+
+    // format date for csv and print, time is shown in the given zone when printing
+    pub(crate) fn format_date(&self, format: &StringFormat, zone: &ViewZone) -> String {
+        match (&self.time, format) {
+            (None, _) => self.date.format("%Y-%m-%d").to_string(),
+            (Some(time), StringFormat::Csv) => time.to_rfc3339_opts(SecondsFormat::Secs, true),
+            (Some(time), StringFormat::Print) => zone.format(time, "%Y-%m-%d %H:%M %Z"),
+        }
+    }
+
+    // fields of the csv row: date, description, category, timezone and tags
+    fn csv_record(&self) -> [String; 5] {
+        [
+            self.format_date(&StringFormat::Csv, &ViewZone::Local),
+            self.description.clone(),
+            self.format_category(StringFormat::Csv),
+            self.timezone.clone(),
+            self.format_tags(&StringFormat::Csv),
+        ]
+    }
+
     // format Event to proper string for csv and print
     pub fn format_to_string(&self, format: StringFormat) -> String {
-        let date = self.date.format("%Y-%m-%d").to_string();
+        self.format_to_string_in(format, &ViewZone::Local)
+    }
+
+    // format Event to proper string for csv and print, times shown in the given zone
+    pub fn format_to_string_in(&self, format: StringFormat, zone: &ViewZone) -> String {
+        let date = self.format_date(&format, zone);
         let description_string = if self.description.is_empty() {
             String::new()
         } else {
             self.description.clone()
         };
         let category_string = self.format_category(format.clone());
+        let tags_string = self.format_tags(&format);
         match format {
-            StringFormat::Print => {
+            StringFormat::Print if tags_string.is_empty() => {
                 format!("{}: {}, {}", date, description_string, category_string)
             }
+            StringFormat::Print => {
+                format!("{}: {}, {} {}", date, description_string, category_string, tags_string)
+            }
+            // csv writer quotes fields having commas or quotes
             StringFormat::Csv => {
-                format!("{},{},{}", date, description_string, category_string)
+                let mut wtr = WriterBuilder::new().has_headers(false).from_writer(Vec::new());
+                let row = wtr.write_record(self.csv_record()).ok().and_then(|_| wtr.into_inner().ok()).unwrap_or_default();
+                String::from_utf8_lossy(&row).trim_end().to_string()
             }
         }
     }
@@ -94,8 +215,9 @@ pub fn read_csv(file_path: &str) -> Result<Vec<Event>, Error> {
     let mut events = Vec::new();
 
     // safe open file if no error
-    let file = File::open(&file_path)?;
-    let mut rdr = csv::Reader::from_reader(file);
+    let file = File::open(file_path)?;
+    // flexible, older files dont have all columns
+    let mut rdr = ReaderBuilder::new().flexible(true).from_reader(file);
 
     for result in rdr.records() {
         // go through readings, note about error-lines, but still continue
@@ -111,9 +233,11 @@ pub fn read_csv(file_path: &str) -> Result<Vec<Event>, Error> {
         let date_str = record.get(0).unwrap_or_default();
         let description_str = record.get(1).unwrap_or_default();
         let category_str = record.get(2).unwrap_or_default();
+        let timezone_str = record.get(3).unwrap_or_default().trim();
+        let tags_str = record.get(4).unwrap_or_default();
 
         // if date is not in correct form, note about error-line, but still continue
-        let parsed_date = match Event::test_date(date_str) {
+        let (parsed_date, parsed_time) = match Event::parse_date_field(date_str) {
             Ok(date) => date,
             Err(err) => {
                 eprintln!("Error parsing date: {}", err);
@@ -122,26 +246,42 @@ pub fn read_csv(file_path: &str) -> Result<Vec<Event>, Error> {
         };
 
         // if categorys are not in correct form, note about error-line, but still continue
-        let (primary, secondary) = match parse_string(category_str, '/') {
-            Ok((primary, secondary)) => (primary, secondary),
+        let category = match parse_category(category_str, '/') {
+            Ok(category) => category,
             Err(err) => {
                 eprintln!("Invalid input category format: {}", err);
                 continue; // Skip to the next record on error
             }
         };
 
+        // if time zone is unknown, note about error-line, but still continue
+        if !timezone_str.is_empty() {
+            if let Err(err) = parse_zone(timezone_str) {
+                eprintln!("Invalid time zone: {}", err);
+                continue;
+            }
+        }
+
         // create event and push it in the event vector
-        let event = Event::new(parsed_date, description_str.to_string(), primary, secondary);
+        let mut event = Event::new(parsed_date, description_str.to_string(), category).with_tags(parse_tags(tags_str));
+        if let Some(time) = parsed_time {
+            event = event.with_time(time, timezone_str.to_string());
+        }
         events.push(event);
     }
     Ok(events)
 }
 
 // print all given vector events in order from oldest to latest
-pub fn print_events(events: &mut Vec<Event>) {
+pub fn print_events(events: &mut [Event]) {
+    print_events_in(events, &ViewZone::Local);
+}
+
+// print all given vector events in order from oldest to latest, times shown in the given zone
+pub fn print_events_in(events: &mut [Event], zone: &ViewZone) {
     events.sort();
-    for event in events {
-        println!("{}", event.format_to_string(StringFormat::Print));
+    for event in events.iter() {
+        println!("{}", event.format_to_string_in(StringFormat::Print, zone));
     }
 }
 
@@ -149,17 +289,18 @@ pub fn print_events(events: &mut Vec<Event>) {
 pub fn delete_events(filepath: &str, orig: &[Event], events_to_delete: &[Event]) -> Result<(),Box<dyn StdError>> {
     // filter all events that arent in the delete-vector
     let remaining_events: Vec<&Event> = orig.iter().filter(|event| !events_to_delete.contains(event)).collect();
+    write_csv(filepath, remaining_events)
+}
+
+// write the file over with given events
+pub fn write_csv<'a>(filepath: &str, events: impl IntoIterator<Item = &'a Event>) -> Result<(),Box<dyn StdError>> {
     // reset the file and write the header in it while testing for errors
     let mut wtr = WriterBuilder::new().from_path(filepath)?;
-    wtr.write_record(&["date", "description", "category"])?;
+    wtr.write_record(["date", "description", "category", "timezone", "tags"])?;
 
     // write every event row by row to the file
-    for event in remaining_events {
-        wtr.write_record(&[
-            &event.date.format("%Y-%m-%d").to_string(),
-            &event.description,
-            &event.format_category(StringFormat::Csv),
-        ])?;
+    for event in events {
+        wtr.write_record(event.csv_record())?;
     }
 
     // make sure all rows are written in the file
@@ -175,12 +316,23 @@ pub fn filter_by_date(
     results: &mut Vec<Event>,
     date_str: &str,
     comparison: DateComparison,
+) -> Result<(), ParseError> {
+    filter_by_date_in(orig, results, date_str, comparison, &ViewZone::Local)
+}
+
+// add filtered Events to results vector, dates and today are taken in the given zone
+pub fn filter_by_date_in(
+    orig: &[Event],
+    results: &mut Vec<Event>,
+    date_str: &str,
+    comparison: DateComparison,
+    zone: &ViewZone,
 ) -> Result<(), ParseError> {
     // if no given date, use todays date instead
     // use todays date
-    let mut given_date = chrono::Local::now().naive_local().date();
-    let mut before_date = chrono::Local::now().naive_local().date();
-    let mut after_date = chrono::Local::now().naive_local().date();
+    let mut given_date = zone.today();
+    let mut before_date = given_date;
+    let mut after_date = given_date;
 
     if comparison == DateComparison::BeforeAfter {
         let mut dates = date_str.split(',');
@@ -197,30 +349,31 @@ pub fn filter_by_date(
     // create temp vector for gathering filtered events and filter by given comparison
     let mut temp_results: Vec<Event> = Vec::new();
     for event in orig {
+        let event_date = event.date_in(zone);
         match comparison {
             DateComparison::Before => {
-                if event.date < given_date {
+                if event_date < given_date {
                     temp_results.push(event.clone());
                 }
             }
             DateComparison::After => {
-                if event.date > given_date {
+                if event_date > given_date {
                     temp_results.push(event.clone());
                 }
             }
             DateComparison::BeforeAfter => {
                 if after_date > before_date {
-                    if event.date < before_date || event.date > after_date {
+                    if event_date < before_date || event_date > after_date {
                         temp_results.push(event.clone());
                     }
                 } else {
-                    if event.date < before_date && event.date > after_date {
+                    if event_date < before_date && event_date > after_date {
                         temp_results.push(event.clone());
                     }
                 }
             }
             DateComparison::Exact | DateComparison::Today => {
-                if event.date == given_date {
+                if event_date == given_date {
                     temp_results.push(event.clone());
                 }
             }
@@ -240,65 +393,131 @@ pub fn filter_by_date(
     Ok(())
 }
 
-// split the string in 2 parts
-pub fn parse_string(categories: &str, splitter: char) -> Result<(String, String), String> {
-    // split the given string using splitter
-    let mut parts = categories.split(splitter);
+// split the category string to category path of any depth
+pub fn parse_category(categories: &str, splitter: char) -> Result<Vec<String>, String> {
+    // no category
+    if categories.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    let parts: Vec<String> = categories.split(splitter).map(|part| part.trim().to_string()).collect();
 
-    // create strings from split or empty string
-    let part1 = parts.next().unwrap_or_default().trim().to_string();
-    let part2 = parts.next().unwrap_or_default().trim().to_string();
+    // every level needs a name, "apple//macos" is most likely a typo
+    if parts.iter().any(|part| part.is_empty()) {
+        return Err(format!("Empty level in category: {}", categories));
+    }
+    Ok(parts)
+}
+
+// check if category filter matches the category path. Filter can start from any level of the path
+// and its last level matches by start of the name, so "apple" and "vendor/app" both match "vendor/apple/macos"
+pub fn category_matches(category: &[String], filter: &[&str]) -> bool {
+    category_matches_with(category, filter, MatchOptions::default())
+}
 
-    // if more than 2 split results, result error
-    if parts.next().is_some() {
-        return Err("Too many parts in category for the split".to_string());
+// check if category filter matches the category path, filter is expected normalized with the options
+pub fn category_matches_with(category: &[String], filter: &[&str], options: MatchOptions) -> bool {
+    if filter.is_empty() || filter.len() > category.len() {
+        return false;
     }
-    Ok((part1, part2))
+    let last = filter.len() - 1;
+    category.windows(filter.len()).any(|window| {
+        window.iter().zip(filter).enumerate().all(|(level, (name, wanted))| {
+            let name = options.normalize(name);
+            if level == last {
+                name.starts_with(wanted)
+            } else {
+                name == *wanted
+            }
+        })
+    })
 }
 
 // filter all events by category or description
 pub fn filter_by_string(orig: &[Event], results: &mut Vec<Event>, input: &str, excluded: bool, category: bool) {
-    let lower_input = input.to_lowercase();
-    // split given category_string to categories
-    let categories: Vec<&str> = lower_input.split(',').map(|s| s.trim()).collect();
+    filter_by_string_with(orig, results, input, excluded, category, &CategoryRegistry::default());
+}
+
+// filter all events by category or description, category aliases are resolved with the registry
+pub fn filter_by_string_with(orig: &[Event], results: &mut Vec<Event>, input: &str, excluded: bool, category: bool, registry: &CategoryRegistry) {
+    // default prefix matching has no patterns to compile, so it cannot fail
+    let _ = filter_by_string_matching(orig, results, input, excluded, category, registry, MatchOptions::default());
+}
+
+// filter all events by category or description with the match options. Prefix matching of categories
+// works by levels, other modes match the whole category path like "microsoft/dotnet". Categories
+// are separated by commas, except a regex which is used whole. Error if a pattern is invalid
+pub fn filter_by_string_matching(orig: &[Event], results: &mut Vec<Event>, input: &str, excluded: bool, category: bool, registry: &CategoryRegistry, options: MatchOptions) -> Result<(), String> {
+    // split given category_string to categories and categories to levels, "ms/dotnet" -> "microsoft/dotnet"
+    let patterns: Vec<&str> = if category && options.mode != MatchMode::Regex { input.split(',').map(str::trim).collect() } else { vec![input] };
+    let resolved: Vec<Vec<String>> = patterns
+        .iter()
+        .map(|s| {
+            let levels: Vec<String> = s.split('/').map(|level| level.trim().to_string()).collect();
+            if matches!(options.mode, MatchMode::Prefix | MatchMode::Exact) { registry.resolve(&levels) } else { levels }
+        })
+        .collect();
+    let normalized: Vec<Vec<String>> = resolved
+        .iter()
+        .map(|levels| levels.iter().map(|level| options.normalize(level)).collect())
+        .collect();
+    let categories: Vec<Vec<&str>> = normalized
+        .iter()
+        .map(|levels| levels.iter().map(String::as_str).collect())
+        .collect();
+    // compile patterns once for all events
+    let matchers = if category && options.mode != MatchMode::Prefix {
+        resolved.iter().map(|levels| TextMatcher::new(&levels.join("/"), options)).collect::<Result<Vec<_>, _>>()?
+    } else if category {
+        Vec::new()
+    } else {
+        vec![TextMatcher::new(input, options)?]
+    };
+    let category_filter_matches = |event: &Event| {
+        if options.mode == MatchMode::Prefix {
+            categories.iter().any(|filter| category_matches_with(&event.category, filter, options))
+        } else {
+            let path = event.category.join("/");
+            matchers.iter().any(|matcher| matcher.is_match(&path))
+        }
+    };
+
     // go through events in orig
     for event in orig {
-        let include_event: bool;
         // if string is category
-        if category {
-            // compare the categories to event categies
-            let primary_matches = categories.iter().any(|&category| {
-                event.primary_category.to_lowercase().starts_with(category)
-
-            });
-            let secondary_matches = categories.iter().any(|&category| {
-                event.secondary_category.to_lowercase().starts_with(category)
-
-            });
+        let include_event = if category {
+            // compare the categories to event category path
+            let matches = category_filter_matches(event);
 
             // create boolean depending if excluded is active
-            include_event = if excluded {
-                !(primary_matches || secondary_matches)
+            if excluded {
+                !matches
             } else {
-                primary_matches || secondary_matches
-            };
+                matches
+            }
         // must be description if not category
         } else {
-            include_event = event.description.to_lowercase().starts_with(&lower_input)
-        }
+            matchers.iter().any(|matcher| matcher.is_match(&event.description))
+        };
         // add event in result list if its not added already
         if include_event && !results.contains(event) {
             results.push(event.clone());
         }
-        // go through all events in results to remove all events with category
-        if category && excluded {
-            results.retain(|event| {
-                let primary_matches = !event.primary_category.is_empty() && categories.iter().any(|&cat| event.primary_category.to_lowercase().starts_with(cat));
-                let secondary_matches = !event.secondary_category.is_empty() && categories.iter().any(|&cat| event.secondary_category.to_lowercase().starts_with(cat));
-                !(primary_matches || secondary_matches)
-            });
-        }
     }
+    // remove all events with category from results
+    if category && excluded {
+        results.retain(|event| !category_filter_matches(event));
+    }
+    Ok(())
+}
+
+// FNV-1a hash, same on every run and platform unlike std hashers
+pub(crate) fn stable_hash(text: &str) -> u32 {
+    let mut hash: u32 = 0x811c9dc5;
+    for byte in text.bytes() {
+        hash ^= u32::from(byte);
+        hash = hash.wrapping_mul(0x01000193);
+    }
+    hash
 }
 
 // validate to match "YYYY-mm-dd" format (NaiveDate accepts 2023-3-3 instead of 2023-03-03)
@@ -313,16 +532,15 @@ pub fn validate_date_format(date_str: &str) -> bool {
 // simple open file for append
 pub fn open_file_for_append(filepath: &str) -> io::Result<File> {
     OpenOptions::new()
-        .write(true)
         .append(true)
         .open(filepath)
 }
 
-// simple append to given file. Meant to use with open_file_for_append()
-pub fn append_to_csv(file: &mut File, new_row: String) -> io::Result<()> {
-    file.write_all(new_row.as_bytes())?;
-    file.write_all(b"\n")?;
-    Ok(())
+// append event as csv row to given file, fields are quoted when needed. Meant to use with open_file_for_append()
+pub fn append_to_csv(file: &mut File, event: &Event) -> io::Result<()> {
+    let mut wtr = WriterBuilder::new().has_headers(false).from_writer(file);
+    wtr.write_record(event.csv_record())?;
+    wtr.flush()
 }
 
 #[cfg(test)]
@@ -330,43 +548,6 @@ mod tests {
     // import necessary items for testing
     use super::*;
 
-    //
-    //  parse_string() tests:
-    //
-
-    #[test]
-    fn test_parse_string_valid_input() {
-        // test input with "2 parameters"
-        let input = "first,second";
-        let result = parse_string(input, ',');
-        assert_eq!(result, Ok(("first".to_string(), "second".to_string())));
-    }
-
-    #[test]
-    fn test_parse_string_single_part() {
-        // test input with "1 parameter"
-        let input = "only";
-        let result = parse_string(input, ',');
-        assert_eq!(result, Ok(("only".to_string(), "".to_string())));
-    }
-
-    #[test]
-    fn test_parse_string_empty_input() {
-        // test input with empty string
-        let input = "";
-        let result = parse_string(input, ',');
-        assert_eq!(result, Ok(("".to_string(), "".to_string())));
-    }
-
-    #[test]
-    fn test_parse_string_too_many_parts() {
-        // test input with "too many parameters"
-        let input = "first,second,third";
-        let result = parse_string(input, ',');
-        assert!(result.is_err()); // Expecting an error
-        assert_eq!(result.unwrap_err(), "Too many parts in category for the split".to_string());
-    }
-
     //
     //  Event tests:
     //
@@ -379,7 +560,7 @@ mod tests {
         let primary_category = "testprimary".to_string();
         let secondary_category = "testsecondary".to_string();
 
-        let event = Event::new(date, description.clone(), primary_category.clone(), secondary_category.clone());
+        let event = Event::new(date, description.clone(), vec![primary_category.clone(), secondary_category.clone()]);
 
         let formatted_string = event.format_to_string(StringFormat::Print);
 
@@ -395,15 +576,43 @@ mod tests {
         let primary_category = "testprimary".to_string();
         let secondary_category = "testsecondary".to_string();
 
-        let event = Event::new(date, description.clone(), primary_category.clone(), secondary_category.clone());
+        let event = Event::new(date, description.clone(), vec![primary_category.clone(), secondary_category.clone()]);
 
         let formatted_string = event.format_to_string(StringFormat::Csv);
 
-        let expected_string = format!("{},{},{}", date.format("%Y-%m-%d"), description, format!("{}/{}", primary_category, secondary_category));
+        let expected_string = format!("{},{},{}/{},,", date.format("%Y-%m-%d"), description, primary_category, secondary_category);
 
         assert_eq!(formatted_string, expected_string);
     }
 
+    #[test]
+    fn test_event_with_time_csv_and_zone_date() {
+        let (date, time) = Event::parse_date_field("2024-07-01T23:30:00-04:00").expect("Valid time");
+        let event = Event::new(date, "release".to_string(), vec!["rust".to_string()])
+            .with_time(time.expect("Time is parsed"), "America/New_York".to_string());
+
+        assert_eq!(event.format_to_string(StringFormat::Csv), "2024-07-01T23:30:00-04:00,release,rust,America/New_York,");
+        // already next day in Helsinki
+        let helsinki = ViewZone::parse("Europe/Helsinki").expect("Valid zone");
+        assert_eq!(event.date_in(&helsinki), NaiveDate::from_ymd_opt(2024, 7, 2).expect("Valid date"));
+    }
+
+    #[test]
+    fn test_append_to_csv_quotes_fields() {
+        let date = NaiveDate::from_ymd_opt(2024, 3, 1).expect("Valid date");
+        let event = Event::new(date, "Hello, \"world\"".to_string(), vec!["rust".to_string()]).with_tags(vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(event.format_to_string(StringFormat::Csv), "2024-03-01,\"Hello, \"\"world\"\"\",rust,,a b");
+
+        let path = std::env::temp_dir().join(format!("days-append-{}.csv", std::process::id()));
+        let path = path.to_str().expect("Valid path");
+        write_csv(path, &[]).expect("File is written");
+        let mut file = open_file_for_append(path).expect("File is opened");
+        append_to_csv(&mut file, &event).expect("Event is appended");
+        let events = read_csv(path).expect("File is read");
+        let _ = std::fs::remove_file(path);
+        assert_eq!(events, vec![event]);
+    }
+
     //
     //  validate_date_format() tests:
     //
@@ -426,9 +635,9 @@ mod tests {
 
     fn create_test_events() -> Vec<Event> {
         vec![
-            Event::new(NaiveDate::from_ymd_opt(2022, 4, 1).expect("Valid date"), "event1".to_string(), "work".to_string(), "".to_string()),
-            Event::new(NaiveDate::from_ymd_opt(2022, 4, 15).expect("Valid date"), "event2".to_string(), "study".to_string(), "homework".to_string()),
-            Event::new(get_today(), "event3".to_string(), "exercise".to_string(), "running".to_string()),
+            Event::new(NaiveDate::from_ymd_opt(2022, 4, 1).expect("Valid date"), "event1".to_string(), vec!["work".to_string()]),
+            Event::new(NaiveDate::from_ymd_opt(2022, 4, 15).expect("Valid date"), "event2".to_string(), vec!["study".to_string(), "homework".to_string()]),
+            Event::new(get_today(), "event3".to_string(), vec!["exercise".to_string(), "running".to_string()]),
         ]
     }
 
@@ -467,6 +676,68 @@ mod tests {
         assert_eq!(results[0].description, "event2");
     }
 
+    #[test]
+    fn test_filter_by_string_category_levels() {
+        let mut events = create_test_events();
+        let macos = vec!["vendor".to_string(), "apple".to_string(), "macos".to_string()];
+        events.push(Event::new(NaiveDate::from_ymd_opt(2021, 10, 25).expect("Valid date"), "event4".to_string(), macos));
+
+        // prefix path from the top, path starting from lower level and start of a name
+        for filter in ["vendor/apple", "apple/macos", "vendor/app", "mac"] {
+            let mut results = Vec::new();
+            filter_by_string(&events, &mut results, filter, false, true);
+            assert_eq!(results.len(), 1, "filter {}", filter);
+            assert_eq!(results[0].description, "event4");
+        }
+
+        // levels must follow each other
+        let mut results = Vec::new();
+        filter_by_string(&events, &mut results, "vendor/macos", false, true);
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn test_filter_by_string_category_alias() {
+        let events = create_test_events();
+        let mut registry = CategoryRegistry::default();
+        registry.declare(vec!["study".to_string()], &["school"], "", "");
+        let mut results = Vec::new();
+        filter_by_string_with(&events, &mut results, "school/home", false, true, &registry);
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].description, "event2");
+    }
+
+    #[test]
+    fn test_filter_by_string_matching_modes() {
+        let events = create_test_events();
+        let registry = CategoryRegistry::default();
+        let options = |mode, case_sensitive| MatchOptions { mode, case_sensitive, ignore_accents: false };
+
+        // other modes than prefix match the whole category path
+        let mut results = Vec::new();
+        filter_by_string_matching(&events, &mut results, "*/home*", false, true, &registry, options(MatchMode::Glob, false)).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].description, "event2");
+
+        let mut results = Vec::new();
+        filter_by_string_matching(&events, &mut results, "ENT3", false, false, &registry, options(MatchMode::Contains, false)).unwrap();
+        assert_eq!(results.len(), 1);
+
+        let mut results = Vec::new();
+        filter_by_string_matching(&events, &mut results, "EVENT", false, false, &registry, options(MatchMode::Prefix, true)).unwrap();
+        assert!(results.is_empty());
+
+        assert!(filter_by_string_matching(&events, &mut results, "(", false, false, &registry, options(MatchMode::Regex, false)).is_err());
+    }
+
+    #[test]
+    fn test_parse_category_any_depth() {
+        assert_eq!(parse_category("vendor/apple/macos", '/'), Ok(vec!["vendor".to_string(), "apple".to_string(), "macos".to_string()]));
+        assert_eq!(parse_category("", '/'), Ok(Vec::new()));
+        assert!(parse_category("vendor//macos", '/').is_err());
+    }
+
     //
     // filter_by_date() tests
     //
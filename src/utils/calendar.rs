@@ -0,0 +1,115 @@
+use std::collections::BTreeSet;
+use chrono::{Datelike, Months, NaiveDate};
+use crate::utils::color::Palette;
+
+// visible width of one month grid, 7 days of 3 chars: day number and mark
+pub const MONTH_WIDTH: usize = 21;
+// gap between months shown side by side
+const MONTH_GAP: &str = "   ";
+// day marks in calendar
+const EVENT_MARK: char = '*';
+const BOLD: &str = "1";
+const TODAY: &str = "7";
+
+// parse month in format YYYY-MM to its first day
+pub fn parse_month(month_str: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(&format!("{}-01", month_str.trim()), "%Y-%m-%d")
+        .map_err(|_| format!("Invalid month: {}. Use format YYYY-MM", month_str))
+}
+
+// first day of the month moved by given count of months
+pub fn shift_month(first_day: NaiveDate, months: i32) -> NaiveDate {
+    let shifted = if months >= 0 {
+        first_day.checked_add_months(Months::new(months.unsigned_abs()))
+    } else {
+        first_day.checked_sub_months(Months::new(months.unsigned_abs()))
+    };
+    shifted.unwrap_or(first_day).with_day(1).unwrap_or(first_day)
+}
+
+// last day of the month of given date
+pub fn last_day_of_month(date: NaiveDate) -> NaiveDate {
+    shift_month(date.with_day(1).unwrap_or(date), 1).pred_opt().unwrap_or(date)
+}
+
+// draw cal(1) style month grid starting from monday. Days with events are marked with '*',
+// today is highlighted when colors are enabled. Every line has visible width of MONTH_WIDTH
+pub fn month_grid(first_day: NaiveDate, marked: &BTreeSet<NaiveDate>, today: NaiveDate, palette: &Palette) -> Vec<String> {
+    let title = first_day.format("%B %Y").to_string();
+    let left = (MONTH_WIDTH.saturating_sub(title.len())) / 2;
+    let mut lines = vec![
+        format!("{:<width$}", format!("{}{}", " ".repeat(left), title), width = MONTH_WIDTH),
+        "Mo Tu We Th Fr Sa Su ".to_string(),
+    ];
+
+    // empty cells before the first day
+    let mut cells: Vec<String> = vec!["   ".to_string(); first_day.weekday().num_days_from_monday() as usize];
+    let mut day = first_day;
+    while day.month() == first_day.month() {
+        let mark = if marked.contains(&day) { EVENT_MARK } else { ' ' };
+        let number = format!("{:>2}", day.day());
+        let number = if day == today {
+            palette.paint(&number, Some(TODAY))
+        } else if marked.contains(&day) {
+            palette.paint(&number, Some(BOLD))
+        } else {
+            number
+        };
+        cells.push(format!("{}{}", number, mark));
+        day = match day.succ_opt() {
+            Some(next) => next,
+            None => break,
+        };
+    }
+
+    // fill short last week with empty cells
+    for week in cells.chunks(7) {
+        lines.push(format!("{}{}", week.concat(), "   ".repeat(7 - week.len())));
+    }
+    // always 6 weeks, so months side by side have the same height
+    while lines.len() < 8 {
+        lines.push(" ".repeat(MONTH_WIDTH));
+    }
+    lines
+}
+
+// put month grids side by side
+pub fn join_months(grids: &[Vec<String>]) -> Vec<String> {
+    let height = grids.iter().map(Vec::len).max().unwrap_or(0);
+    (0..height)
+        .map(|row| {
+            let parts: Vec<&str> = grids.iter().map(|grid| grid.get(row).map(String::as_str).unwrap_or_default()).collect();
+            parts.join(MONTH_GAP).trim_end().to_string()
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("Valid date")
+    }
+
+    #[test]
+    fn test_month_grid_marks_event_days() {
+        let marked: BTreeSet<NaiveDate> = [date(2023, 11, 14)].into_iter().collect();
+        let grid = month_grid(date(2023, 11, 1), &marked, date(2024, 1, 1), &Palette::plain());
+
+        assert_eq!(grid[0], "    November 2023    ");
+        assert_eq!(grid[1], "Mo Tu We Th Fr Sa Su ");
+        assert_eq!(grid[2], "       1  2  3  4  5 ");
+        assert_eq!(grid[4], "13 14*15 16 17 18 19 ");
+        assert_eq!(grid[6], "27 28 29 30          ");
+        assert_eq!(grid.len(), 8);
+    }
+
+    #[test]
+    fn test_shift_month_and_last_day() {
+        assert_eq!(shift_month(date(2024, 1, 1), -1), date(2023, 12, 1));
+        assert_eq!(shift_month(date(2023, 12, 1), 1), date(2024, 1, 1));
+        assert_eq!(last_day_of_month(date(2024, 2, 10)), date(2024, 2, 29));
+        assert!(parse_month("2024-13").is_err());
+    }
+}
@@ -0,0 +1,131 @@
+use std::collections::BTreeMap;
+use crate::utils::all_utils::Event;
+use crate::utils::normalize::fold_path;
+
+// one level of the category tree, count includes events of all descendants
+#[derive(Debug, Default, PartialEq)]
+pub struct CategoryNode {
+    pub count: usize,
+    pub children: BTreeMap<String, CategoryNode>,
+}
+
+impl CategoryNode {
+    // add one event with given category path under this node
+    fn insert(&mut self, path: &[String]) {
+        self.count += 1;
+        if let Some((first, rest)) = path.split_first() {
+            self.children.entry(first.clone()).or_default().insert(rest);
+        }
+    }
+}
+
+// build category tree from events, root count is the count of all events
+pub fn build_category_tree(events: &[Event]) -> CategoryNode {
+    let mut root = CategoryNode::default();
+    for event in events {
+        root.insert(event.category());
+    }
+    root
+}
+
+// count events per full category path, events without category are counted under "/"
+pub fn count_categories(events: &[Event]) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for event in events {
+        let path = if event.category().is_empty() {
+            "/".to_string()
+        } else {
+            event.category().join("/")
+        };
+        *counts.entry(path).or_insert(0) += 1;
+    }
+    counts
+}
+
+// format tree to indented lines "name (count)", uncategorized events are shown as "/"
+pub fn format_category_tree(root: &CategoryNode) -> Vec<String> {
+    let mut lines = Vec::new();
+    for (name, child) in &root.children {
+        format_node(name, child, 0, &mut lines);
+    }
+    let categorized: usize = root.children.values().map(|child| child.count).sum();
+    if root.count > categorized {
+        lines.push(format!("/ ({})", root.count - categorized));
+    }
+    lines
+}
+
+// move events under any of the sources to the target, keeping the levels below the source.
+// renaming "apple" to "vendor/apple" moves "apple/macos" to "vendor/apple/macos".
+// returns changed events as (old, new) pairs
+pub fn move_categories(events: &[Event], sources: &[Vec<String>], target: &[String]) -> Vec<(Event, Event)> {
+    let mut changes = Vec::new();
+    for event in events {
+        if let Some(source) = sources.iter().find(|source| !source.is_empty() && fold_path(event.category()).starts_with(&fold_path(source))) {
+            let mut category = target.to_vec();
+            category.extend_from_slice(&event.category()[source.len()..]);
+            if category != event.category() {
+                changes.push((event.clone(), event.clone().with_category(category)));
+            }
+        }
+    }
+    changes
+}
+
+fn format_node(name: &str, node: &CategoryNode, depth: usize, lines: &mut Vec<String>) {
+    lines.push(format!("{}{} ({})", "  ".repeat(depth), name, node.count));
+    for (child_name, child) in &node.children {
+        format_node(child_name, child, depth + 1, lines);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn event(category: &[&str]) -> Event {
+        let date = NaiveDate::from_ymd_opt(2024, 4, 15).expect("Valid date");
+        Event::new(date, "event".to_string(), category.iter().map(|level| level.to_string()).collect())
+    }
+
+    #[test]
+    fn test_category_tree_counts_descendants() {
+        let events = vec![
+            event(&["vendor", "apple", "macos"]),
+            event(&["vendor", "apple", "ios"]),
+            event(&["vendor", "microsoft"]),
+            event(&[]),
+        ];
+        let lines = format_category_tree(&build_category_tree(&events));
+
+        assert_eq!(lines, vec![
+            "vendor (3)",
+            "  apple (2)",
+            "    ios (1)",
+            "    macos (1)",
+            "  microsoft (1)",
+            "/ (1)",
+        ]);
+    }
+
+    #[test]
+    fn test_move_categories_keeps_subcategories() {
+        let events = vec![event(&["apple", "macos"]), event(&["apple"]), event(&["applesauce"]), event(&["ms"])];
+        let sources = vec![vec!["apple".to_string()], vec!["ms".to_string()]];
+        let target = vec!["vendor".to_string(), "apple".to_string()];
+        let changes = move_categories(&events, &sources, &target);
+
+        let moved: Vec<String> = changes.iter().map(|(_, new)| new.category().join("/")).collect();
+        assert_eq!(moved, vec!["vendor/apple/macos", "vendor/apple", "vendor/apple"]);
+    }
+
+    #[test]
+    fn test_count_categories_full_paths() {
+        let events = vec![event(&["rust"]), event(&["rust"]), event(&[])];
+        let counts = count_categories(&events);
+
+        assert_eq!(counts.get("rust"), Some(&2));
+        assert_eq!(counts.get("/"), Some(&1));
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::BTreeMap;
+use chrono::{Datelike, Duration, NaiveDate};
+use crate::utils::all_utils::Event;
+use crate::utils::timezone::ViewZone;
+
+// heatmap cells from no events to most events
+const HEAT_LEVELS: [char; 5] = ['·', '░', '▒', '▓', '█'];
+const WEEKDAYS: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+// timeline marks
+const AXIS: char = '-';
+const MARK: char = '*';
+const STEM: char = '|';
+// free cell in timeline rows, spaces inside labels are not free
+const FREE: char = '\0';
+
+// count events per day as seen in the zone
+pub fn count_per_day(events: &[Event], zone: &ViewZone) -> BTreeMap<NaiveDate, usize> {
+    let mut counts = BTreeMap::new();
+    for event in events {
+        *counts.entry(event.date_in(zone)).or_insert(0) += 1;
+    }
+    counts
+}
+
+// heat level 0-4 of the count, relative to the busiest day
+fn heat_level(count: usize, max: usize) -> usize {
+    if count == 0 || max == 0 {
+        0
+    } else {
+        (count * 4).div_ceil(max).clamp(1, 4)
+    }
+}
+
+// draw GitHub style heatmap of the year: one column per week starting from monday, one row per weekday
+pub fn heatmap(events: &[Event], year: i32, zone: &ViewZone) -> Vec<String> {
+    let counts = count_per_day(events, zone);
+    let (Some(first), Some(last)) = (NaiveDate::from_ymd_opt(year, 1, 1), NaiveDate::from_ymd_opt(year, 12, 31)) else {
+        return Vec::new();
+    };
+    let max = counts.range(first..=last).map(|(_, count)| *count).max().unwrap_or(0);
+    let start = first - Duration::days(i64::from(first.weekday().num_days_from_monday()));
+    let weeks = ((last - start).num_days() / 7 + 1) as usize;
+
+    // month names above the week where the month starts, if there is room
+    let mut months = vec![' '; weeks];
+    for month in 1..=12 {
+        let Some(month_start) = NaiveDate::from_ymd_opt(year, month, 1) else { continue };
+        let column = ((month_start - start).num_days() / 7) as usize;
+        let name = month_start.format("%b").to_string();
+        if months[column..].iter().take(name.len() + 1).all(|c| *c == ' ') {
+            for (offset, c) in name.chars().enumerate() {
+                if let Some(cell) = months.get_mut(column + offset) {
+                    *cell = c;
+                }
+            }
+        }
+    }
+    let mut lines = vec![format!("   {}", months.iter().collect::<String>().trim_end())];
+
+    for (row, weekday) in WEEKDAYS.iter().enumerate() {
+        let cells: String = (0..weeks)
+            .map(|week| {
+                let day = start + Duration::days((week * 7 + row) as i64);
+                if day < first || day > last {
+                    ' '
+                } else {
+                    HEAT_LEVELS[heat_level(counts.get(&day).copied().unwrap_or(0), max)]
+                }
+            })
+            .collect();
+        lines.push(format!("{} {}", weekday, cells.trim_end()));
+    }
+
+    let total: usize = counts.range(first..=last).map(|(_, count)| count).sum();
+    lines.push(String::new());
+    lines.push(format!("   Less {} More   {} events in {}", HEAT_LEVELS.iter().collect::<String>(), total, year));
+    lines
+}
+
+// draw horizontal timeline of given width with events spaced by date. Labels are put on the
+// first row below the axis where they fit, and stems connect the marks to lower labels
+pub fn timeline(events: &[Event], width: usize, zone: &ViewZone) -> Vec<String> {
+    // order by the shown dates, events with times can move to another day in the zone
+    let mut events: Vec<&Event> = events.iter().collect();
+    events.sort_by(|a, b| a.date_in(zone).cmp(&b.date_in(zone)).then_with(|| a.cmp(b)));
+    let (Some(first), Some(last)) = (events.first(), events.last()) else {
+        return Vec::new();
+    };
+    let width = width.max(20);
+    let first_date = first.date_in(zone);
+    let last_date = last.date_in(zone);
+    let span = (last_date - first_date).num_days().max(1);
+
+    // column of every event by its date
+    let columns: Vec<usize> = events
+        .iter()
+        .map(|event| ((event.date_in(zone) - first_date).num_days() * (width as i64 - 1) / span).clamp(0, width as i64 - 1) as usize)
+        .collect();
+
+    // put every label on the first row where it fits with a space before it. Labels start from their
+    // mark, or end to it if there is no room on the right. Labels fitting on neither side are cut to
+    // the wider side
+    let mut rows: Vec<Vec<char>> = Vec::new();
+    let mut label_rows = Vec::new();
+    for (event, column) in events.iter().zip(&columns) {
+        let mut label: Vec<char> = format!("{} {}", event.date_in(zone).format("%Y-%m-%d"), event.description()).chars().collect();
+        let (right, left) = (width - column, column + 1);
+        let room = if label.len() <= right { right } else { left.max(right) };
+        if label.len() > room {
+            label.truncate(room - 1);
+            label.push('…');
+        }
+        let start = if label.len() <= right { *column } else { (column + 1).saturating_sub(label.len()) };
+        let end = start + label.len();
+        let free_row = rows.iter().position(|row| row[start.saturating_sub(1)..end].iter().all(|c| *c == FREE));
+        let row_index = free_row.unwrap_or_else(|| {
+            rows.push(vec![FREE; width]);
+            rows.len() - 1
+        });
+        rows[row_index][start..end].copy_from_slice(&label);
+        label_rows.push(row_index);
+    }
+
+    // stems from the axis down to the row above the label
+    for (column, label_row) in columns.iter().zip(&label_rows) {
+        for row in rows.iter_mut().take(*label_row) {
+            if row[*column] == FREE {
+                row[*column] = STEM;
+            }
+        }
+    }
+
+    let mut axis = vec![AXIS; width];
+    for column in &columns {
+        axis[*column] = MARK;
+    }
+    let start_label = first_date.format("%Y-%m-%d").to_string();
+    let end_label = last_date.format("%Y-%m-%d").to_string();
+    let mut lines = vec![
+        format!("{}{:>pad$}", start_label, end_label, pad = width.saturating_sub(start_label.len())),
+        axis.iter().collect(),
+    ];
+    lines.extend(rows.iter().map(|row| row.iter().map(|c| if *c == FREE { ' ' } else { *c }).collect::<String>().trim_end().to_string()));
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+
+    fn event(year: i32, month: u32, day: u32, description: &str) -> Event {
+        Event::new(NaiveDate::from_ymd_opt(year, month, day).expect("Valid date"), description.to_string(), Vec::new())
+    }
+
+    #[test]
+    fn test_heatmap_levels_and_layout() {
+        let events = vec![event(2024, 1, 1, "a"), event(2024, 1, 1, "b"), event(2024, 1, 3, "c"), event(2023, 12, 31, "other year")];
+        let lines = heatmap(&events, 2024, &ViewZone::Local);
+
+        // 2024 starts on monday
+        assert!(lines[0].starts_with("   Jan"));
+        assert!(lines[1].starts_with("Mo █·"));
+        assert!(lines[3].starts_with("We ▒·"));
+        assert!(lines.last().unwrap().ends_with("3 events in 2024"));
+    }
+
+    #[test]
+    fn test_timeline_spaces_events_and_stacks_labels() {
+        let events = vec![event(2020, 1, 1, "first"), event(2020, 1, 2, "second"), event(2020, 1, 11, "last")];
+        let lines = timeline(&events, 30, &ViewZone::Local);
+
+        assert_eq!(lines[0], "2020-01-01          2020-01-11");
+        assert_eq!(lines[1], "*-*--------------------------*");
+        assert_eq!(lines[2], "2020-01-01 first             |");
+        assert_eq!(lines[3], "  2020-01-02 second          |");
+        // label is moved to the left of its mark at the end of the line
+        assert_eq!(lines[4], "               2020-01-11 last");
+    }
+
+    #[test]
+    fn test_timeline_cuts_long_labels() {
+        let long = "x".repeat(100);
+        let events = vec![event(2020, 1, 1, "first"), event(2020, 1, 6, &long), event(2020, 1, 11, "last")];
+        let lines = timeline(&events, 30, &ViewZone::Local);
+
+        assert!(lines.iter().all(|line| line.chars().count() <= 30));
+        // the long label starts from the middle mark and is cut at the right edge
+        assert!(lines.iter().any(|line| line == &format!("{}2020-01-06 {}…", " ".repeat(14), "x".repeat(4))));
+    }
+
+    #[test]
+    fn test_timeline_orders_by_zone_dates() {
+        // stored dates are in the other order than the dates in utc
+        let tokyo = DateTime::parse_from_rfc3339("2020-01-02T00:30:00+09:00").unwrap();
+        let new_york = DateTime::parse_from_rfc3339("2020-01-01T23:00:00-05:00").unwrap();
+        let events = vec![
+            event(2020, 1, 2, "tokyo").with_time(tokyo, "Asia/Tokyo".to_string()),
+            event(2020, 1, 1, "new york").with_time(new_york, "America/New_York".to_string()),
+        ];
+        let lines = timeline(&events, 30, &ViewZone::Named(chrono_tz::UTC));
+
+        assert_eq!(lines[0], "2020-01-01          2020-01-02");
+        assert_eq!(lines[2], "2020-01-01 tokyo             |");
+    }
+}
@@ -0,0 +1,205 @@
+use std::cmp::Ordering;
+use std::io::IsTerminal;
+use chrono::NaiveDate;
+use crate::utils::all_utils::{stable_hash, Event, StringFormat};
+use crate::utils::registry::CategoryRegistry;
+use crate::utils::timezone::ViewZone;
+
+// colors given to categories without configured color, picked by hash of the primary category
+const HASH_COLORS: [&str; 12] = ["31", "32", "33", "34", "35", "36", "91", "92", "93", "94", "95", "96"];
+const RESET: &str = "\x1b[0m";
+const DIM: &str = "2";
+const BOLD: &str = "1";
+const TODAY: &str = "1;4";
+
+// value of --color
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColorChoice {
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        match input {
+            "auto" => Ok(ColorChoice::Auto),
+            "always" => Ok(ColorChoice::Always),
+            "never" => Ok(ColorChoice::Never),
+            other => Err(format!("Unknown color choice: {}. Use auto, always or never", other)),
+        }
+    }
+
+    // auto uses colors only on terminal and when NO_COLOR is not set
+    pub fn enabled(&self) -> bool {
+        match self {
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+            ColorChoice::Auto => {
+                std::env::var_os("NO_COLOR").is_none_or(|value| value.is_empty()) && std::io::stdout().is_terminal()
+            }
+        }
+    }
+}
+
+// turn color name or #rrggbb to ANSI code
+pub fn parse_color(color: &str) -> Result<String, String> {
+    let code = match color.trim().to_lowercase().as_str() {
+        "black" => "30",
+        "red" => "31",
+        "green" => "32",
+        "yellow" => "33",
+        "blue" => "34",
+        "magenta" => "35",
+        "cyan" => "36",
+        "white" => "37",
+        "gray" | "grey" => "90",
+        "bright-red" => "91",
+        "bright-green" => "92",
+        "bright-yellow" => "93",
+        "bright-blue" => "94",
+        "bright-magenta" => "95",
+        "bright-cyan" => "96",
+        hex if hex.len() == 7 && hex.is_ascii() && hex.starts_with('#') => {
+            let channel = |range| u8::from_str_radix(&hex[range], 16).map_err(|_| format!("Invalid color: {}", color));
+            return Ok(format!("38;2;{};{};{}", channel(1..3)?, channel(3..5)?, channel(5..7)?));
+        }
+        _ => return Err(format!("Invalid color: {}", color)),
+    };
+    Ok(code.to_string())
+}
+
+// colors for printing, plain palette prints text as it is
+#[derive(Debug, Default)]
+pub struct Palette {
+    enabled: bool,
+    // configured colors by category path
+    colors: Vec<(Vec<String>, String)>,
+}
+
+impl Palette {
+    // palette without colors
+    pub fn plain() -> Self {
+        Palette::default()
+    }
+
+    // palette using colors configured in the categories file
+    pub fn new(enabled: bool, registry: &CategoryRegistry) -> Self {
+        let mut colors = Vec::new();
+        for info in registry.categories().iter().filter(|info| !info.color.is_empty()) {
+            match parse_color(&info.color) {
+                Ok(code) => colors.push((info.path.clone(), code)),
+                Err(err) => eprintln!("{} in category {}", err, info.path.join("/")),
+            }
+        }
+        Palette { enabled, colors }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    // color of the category: configured color of the closest category above it, or color by hash
+    pub fn category_color(&self, category: &[String]) -> Option<String> {
+        if category.is_empty() {
+            return None;
+        }
+        let configured = self
+            .colors
+            .iter()
+            .filter(|(path, _)| category.starts_with(path))
+            .max_by_key(|(path, _)| path.len())
+            .map(|(_, code)| code.clone());
+        // hash of the primary category, so the color stays the same on every run
+        configured.or_else(|| Some(HASH_COLORS[stable_hash(&category[0]) as usize % HASH_COLORS.len()].to_string()))
+    }
+
+    // wrap text in ANSI code if colors are enabled
+    pub fn paint(&self, text: &str, code: Option<&str>) -> String {
+        match code {
+            Some(code) if self.enabled && !text.is_empty() => format!("\x1b[{}m{}{}", code, text, RESET),
+            _ => text.to_string(),
+        }
+    }
+
+    // dim past dates, underline today and make future dates bold
+    pub fn paint_date(&self, text: &str, date: NaiveDate, today: NaiveDate) -> String {
+        let code = match date.cmp(&today) {
+            Ordering::Less => DIM,
+            Ordering::Equal => TODAY,
+            Ordering::Greater => BOLD,
+        };
+        self.paint(text, Some(code))
+    }
+
+    // color category text by the category of the event
+    pub fn paint_category(&self, text: &str, event: &Event) -> String {
+        self.paint(text, self.category_color(event.category()).as_deref())
+    }
+}
+
+// format event for printing like StringFormat::Print, with colors
+pub fn format_colored(event: &Event, zone: &ViewZone, palette: &Palette, today: NaiveDate) -> String {
+    let date = palette.paint_date(&event.format_date(&StringFormat::Print, zone), event.date_in(zone), today);
+    let category = palette.paint_category(&event.format_category(StringFormat::Print), event);
+    let tags = event.format_tags(&StringFormat::Print);
+    if tags.is_empty() {
+        format!("{}: {}, {}", date, event.description(), category)
+    } else {
+        format!("{}: {}, {} {}", date, event.description(), category, tags)
+    }
+}
+
+// print all given vector events in order from oldest to latest with colors
+pub fn print_events_colored(events: &mut [Event], zone: &ViewZone, palette: &Palette) {
+    events.sort();
+    let today = zone.today();
+    for event in events.iter() {
+        println!("{}", format_colored(event, zone, palette, today));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(category: &[&str]) -> Event {
+        let date = NaiveDate::from_ymd_opt(2024, 4, 15).expect("Valid date");
+        Event::new(date, "event".to_string(), category.iter().map(|level| level.to_string()).collect())
+    }
+
+    #[test]
+    fn test_plain_palette_matches_print_format() {
+        let event = event(&["rust"]);
+        let today = NaiveDate::from_ymd_opt(2024, 4, 15).expect("Valid date");
+        let formatted = format_colored(&event, &ViewZone::Local, &Palette::plain(), today);
+
+        assert_eq!(formatted, event.format_to_string(StringFormat::Print));
+    }
+
+    #[test]
+    fn test_category_color_configured_and_hashed() {
+        let mut registry = CategoryRegistry::default();
+        registry.declare(vec!["vendor".to_string(), "apple".to_string()], &[], "", "#ff8000");
+        let palette = Palette::new(true, &registry);
+
+        assert_eq!(palette.category_color(event(&["vendor", "apple", "macos"]).category()), Some("38;2;255;128;0".to_string()));
+        // hashed color is stable and the same for the whole primary category
+        let rust = palette.category_color(event(&["rust"]).category());
+        assert_eq!(rust, palette.category_color(event(&["rust", "release"]).category()));
+        assert_eq!(palette.category_color(&[]), None);
+    }
+
+    #[test]
+    fn test_paint_date_relative_to_today() {
+        let palette = Palette::new(true, &CategoryRegistry::default());
+        let today = NaiveDate::from_ymd_opt(2024, 4, 15).expect("Valid date");
+        let yesterday = NaiveDate::from_ymd_opt(2024, 4, 14).expect("Valid date");
+
+        assert_eq!(palette.paint_date("x", yesterday, today), "\x1b[2mx\x1b[0m");
+        assert_eq!(palette.paint_date("x", today, today), "\x1b[1;4mx\x1b[0m");
+        assert!(parse_color("purple").is_err());
+        // non ascii hex is an error, not a panic on slicing
+        assert!(parse_color("#fä000").is_err());
+    }
+}
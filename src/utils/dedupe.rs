@@ -0,0 +1,132 @@
+use crate::utils::all_utils::Event;
+use crate::utils::normalize::fold;
+use crate::utils::registry::edit_distance;
+use crate::utils::timezone::ViewZone;
+
+// which event of a duplicate group survives in --keep
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Keep {
+    First,
+    Last,
+    Longest,
+}
+
+impl Keep {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        match input.trim().to_lowercase().as_str() {
+            "first" => Ok(Keep::First),
+            "last" => Ok(Keep::Last),
+            "longest" => Ok(Keep::Longest),
+            other => Err(format!("Unknown keep: {}. Use first, last or longest", other)),
+        }
+    }
+
+    // position in the group of the surviving event. First and last are in file order,
+    // longest is the longest description and the first of equally long ones
+    pub fn survivor(&self, group: &[usize], events: &[Event]) -> usize {
+        match self {
+            Keep::First => 0,
+            Keep::Last => group.len().saturating_sub(1),
+            Keep::Longest => {
+                let length = |position: &usize| events[group[*position]].description().chars().count();
+                (0..group.len()).rev().max_by_key(length).unwrap_or(0)
+            }
+        }
+    }
+}
+
+// similarity of two descriptions from 0 to 1, case and unicode forms are ignored
+pub fn similarity(a: &str, b: &str) -> f64 {
+    let (a, b) = (fold(a.trim()), fold(b.trim()));
+    let length = a.chars().count().max(b.chars().count());
+    if length == 0 {
+        return 1.0;
+    }
+    1.0 - edit_distance(&a, &b) as f64 / length as f64
+}
+
+// two events are duplicates if they are on the same day with similar descriptions, or have the same
+// description at most within_days apart
+fn is_duplicate(a: &Event, b: &Event, min_similarity: f64, within_days: i64, zone: &ViewZone) -> bool {
+    let days = (a.date_in(zone) - b.date_in(zone)).num_days().abs();
+    (days == 0 && similarity(a.description(), b.description()) >= min_similarity) || (days <= within_days && fold(a.description().trim()) == fold(b.description().trim()))
+}
+
+// groups of duplicate events as indexes to events, each group in file order and groups in date order.
+// Duplicates of duplicates are in the same group
+pub fn find_duplicates(events: &[Event], min_similarity: f64, within_days: i64, zone: &ViewZone) -> Vec<Vec<usize>> {
+    let mut by_date: Vec<usize> = (0..events.len()).collect();
+    by_date.sort_by_key(|index| events[*index].date_in(zone));
+
+    // union find over the events, only events within the days of each other are compared
+    let mut parent: Vec<usize> = (0..events.len()).collect();
+    fn root(parent: &mut [usize], mut index: usize) -> usize {
+        while parent[index] != index {
+            parent[index] = parent[parent[index]];
+            index = parent[index];
+        }
+        index
+    }
+    let max_days = within_days.max(0);
+    for (position, a) in by_date.iter().enumerate() {
+        for b in &by_date[position + 1..] {
+            if (events[*b].date_in(zone) - events[*a].date_in(zone)).num_days() > max_days {
+                break;
+            }
+            if is_duplicate(&events[*a], &events[*b], min_similarity, within_days, zone) {
+                let (root_a, root_b) = (root(&mut parent, *a), root(&mut parent, *b));
+                parent[root_a.max(root_b)] = root_a.min(root_b);
+            }
+        }
+    }
+
+    let mut groups: Vec<Vec<usize>> = Vec::new();
+    for index in 0..events.len() {
+        let group_root = root(&mut parent, index);
+        match groups.iter_mut().find(|group| group[0] == group_root) {
+            Some(group) => group.push(index),
+            None => groups.push(vec![index]),
+        }
+    }
+    groups.retain(|group| group.len() > 1);
+    groups.sort_by_key(|group| group.iter().map(|index| events[*index].date_in(zone)).min());
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn event(day: u32, description: &str) -> Event {
+        let date = NaiveDate::from_ymd_opt(2023, 11, day).expect("Valid date");
+        Event::new(date, description.to_string(), vec!["microsoft".to_string()])
+    }
+
+    #[test]
+    fn test_find_exact_and_fuzzy_duplicates() {
+        let events = vec![
+            event(14, ".NET 8 released"),
+            event(14, ".NET 8 relased"),
+            event(14, "Windows 11 23H2 released"),
+            event(16, ".net 8 released "),
+            event(20, ".NET 8 released"),
+            event(14, ".NET 8 released"),
+        ];
+        let groups = find_duplicates(&events, 0.8, 3, &ViewZone::Local);
+        assert_eq!(groups, vec![vec![0, 1, 3, 5]]);
+        // without the day window only same day duplicates are found
+        assert_eq!(find_duplicates(&events, 0.8, 0, &ViewZone::Local), vec![vec![0, 1, 5]]);
+        assert!(similarity("Uusi näyttö", "UUSI NÄYTTÖ") == 1.0 && similarity("abc", "xyz") == 0.0);
+    }
+
+    #[test]
+    fn test_keep_survivor() {
+        let events = vec![event(14, "Short"), event(14, "Longer one"), event(14, "Longer two")];
+        let group = vec![0, 1, 2];
+        assert_eq!(Keep::First.survivor(&group, &events), 0);
+        assert_eq!(Keep::Last.survivor(&group, &events), 2);
+        assert_eq!(Keep::Longest.survivor(&group, &events), 1);
+        assert!(Keep::parse("middle").is_err());
+    }
+}
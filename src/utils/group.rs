@@ -0,0 +1,94 @@
+use std::collections::BTreeMap;
+use chrono::Datelike;
+use crate::utils::all_utils::{Event, StringFormat};
+use crate::utils::timezone::ViewZone;
+
+// ways to group events in --group-by
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GroupBy {
+    Year,
+    Month,
+    Weekday,
+    Category,
+}
+
+impl GroupBy {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        match input.trim().to_lowercase().as_str() {
+            "year" => Ok(GroupBy::Year),
+            "month" => Ok(GroupBy::Month),
+            "weekday" => Ok(GroupBy::Weekday),
+            "category" => Ok(GroupBy::Category),
+            other => Err(format!("Unknown group: {}. Use year, month, weekday or category", other)),
+        }
+    }
+
+    // sort key and shown name of the group of the event
+    fn key(&self, event: &Event, zone: &ViewZone) -> (u32, String) {
+        let date = event.date_in(zone);
+        match self {
+            GroupBy::Year => (0, date.year().to_string()),
+            GroupBy::Month => (0, date.format("%Y-%m").to_string()),
+            GroupBy::Weekday => (date.weekday().num_days_from_monday(), date.format("%A").to_string()),
+            GroupBy::Category => (0, event.format_category(StringFormat::Print)),
+        }
+    }
+}
+
+// group events to (name, events) pairs. Years and months are in time order, weekdays from monday
+// and categories alphabetically. Events are sorted inside the groups
+pub fn group_events(events: &[Event], group_by: GroupBy, zone: &ViewZone) -> Vec<(String, Vec<Event>)> {
+    let mut groups: BTreeMap<(u32, String), Vec<Event>> = BTreeMap::new();
+    for event in events {
+        groups.entry(group_by.key(event, zone)).or_default().push(event.clone());
+    }
+    groups
+        .into_iter()
+        .map(|((_, name), mut group)| {
+            group.sort();
+            (name, group)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn create_test_events() -> Vec<Event> {
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).expect("Valid date");
+        vec![
+            Event::new(date(2023, 11, 14), ".NET 8 released".to_string(), vec!["microsoft".to_string()]),
+            Event::new(date(2022, 11, 8), ".NET 7 released".to_string(), vec!["microsoft".to_string()]),
+            Event::new(date(2023, 3, 9), "Rust 1.68 released".to_string(), vec!["rust".to_string()]),
+            Event::new(date(2024, 1, 1), "New year".to_string(), Vec::new()),
+        ]
+    }
+
+    #[test]
+    fn test_group_events_by_year_sorts_groups_and_events() {
+        let groups = group_events(&create_test_events(), GroupBy::Year, &ViewZone::Local);
+        let summary: Vec<(&str, Vec<&str>)> = groups.iter().map(|(name, events)| (name.as_str(), events.iter().map(Event::description).collect())).collect();
+
+        assert_eq!(
+            summary,
+            vec![
+                ("2022", vec![".NET 7 released"]),
+                ("2023", vec!["Rust 1.68 released", ".NET 8 released"]),
+                ("2024", vec!["New year"]),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_group_events_by_weekday_and_category() {
+        let weekdays: Vec<String> = group_events(&create_test_events(), GroupBy::Weekday, &ViewZone::Local).into_iter().map(|(name, _)| name).collect();
+        assert_eq!(weekdays, vec!["Monday", "Tuesday", "Thursday"]);
+
+        let categories = group_events(&create_test_events(), GroupBy::Category, &ViewZone::Local);
+        assert_eq!(categories[0].0, "/");
+        assert_eq!((categories[1].0.as_str(), categories[1].1.len()), ("microsoft", 2));
+        assert!(GroupBy::parse("decade").is_err());
+    }
+}
@@ -0,0 +1,19 @@
+use std::error::Error as StdError;
+use std::fs::OpenOptions;
+use std::path::Path;
+use csv::WriterBuilder;
+
+// append a change to the history file as row: time,action,details
+pub fn append_history(filepath: &str, action: &str, details: &str) -> Result<(), Box<dyn StdError>> {
+    // write header only for new file
+    let new_file = !Path::new(filepath).exists();
+    let file = OpenOptions::new().create(true).append(true).open(filepath)?;
+    let mut wtr = WriterBuilder::new().from_writer(file);
+    if new_file {
+        wtr.write_record(["time", "action", "details"])?;
+    }
+    let time = chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true);
+    wtr.write_record([time.as_str(), action, details])?;
+    wtr.flush()?;
+    Ok(())
+}
@@ -0,0 +1,145 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use crate::utils::all_utils::Event;
+
+// category of the holiday events shown with --holidays
+pub const HOLIDAY_CATEGORY: &str = "holiday";
+
+// how the date of a holiday is computed every year
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Rule {
+    // same month and day every year
+    Fixed(u32, u32),
+    // days from easter sunday
+    Easter(i64),
+    // first given weekday on or after the month and day
+    WeekdayFrom(u32, u32, Weekday),
+}
+
+impl Rule {
+    pub fn date_in(&self, year: i32) -> Option<NaiveDate> {
+        match self {
+            Rule::Fixed(month, day) => NaiveDate::from_ymd_opt(year, *month, *day),
+            Rule::Easter(days) => easter_sunday(year)?.checked_add_signed(Duration::days(*days)),
+            Rule::WeekdayFrom(month, day, weekday) => {
+                let start = NaiveDate::from_ymd_opt(year, *month, *day)?;
+                let days = (7 + weekday.num_days_from_monday() - start.weekday().num_days_from_monday()) % 7;
+                start.checked_add_signed(Duration::days(i64::from(days)))
+            }
+        }
+    }
+}
+
+// finnish public holidays and the eves that are days off too
+const FINLAND: &[(&str, Rule)] = &[
+    ("New Year's Day", Rule::Fixed(1, 1)),
+    ("Epiphany", Rule::Fixed(1, 6)),
+    ("Good Friday", Rule::Easter(-2)),
+    ("Easter Sunday", Rule::Easter(0)),
+    ("Easter Monday", Rule::Easter(1)),
+    ("May Day", Rule::Fixed(5, 1)),
+    ("Ascension Day", Rule::Easter(39)),
+    ("Whit Sunday", Rule::Easter(49)),
+    ("Midsummer Eve", Rule::WeekdayFrom(6, 19, Weekday::Fri)),
+    ("Midsummer Day", Rule::WeekdayFrom(6, 20, Weekday::Sat)),
+    ("All Saints' Day", Rule::WeekdayFrom(10, 31, Weekday::Sat)),
+    ("Independence Day", Rule::Fixed(12, 6)),
+    ("Christmas Eve", Rule::Fixed(12, 24)),
+    ("Christmas Day", Rule::Fixed(12, 25)),
+    ("Boxing Day", Rule::Fixed(12, 26)),
+];
+
+// easter sunday of the gregorian calendar with the anonymous gregorian algorithm
+pub fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+// holiday rules of one country, given with --holidays
+#[derive(Debug, Clone, PartialEq)]
+pub struct HolidayCalendar {
+    country: String,
+    rules: &'static [(&'static str, Rule)],
+}
+
+impl HolidayCalendar {
+    // calendar of the country code, only FI is known for now
+    pub fn new(country: &str) -> Result<Self, String> {
+        let country = country.trim().to_uppercase();
+        let rules = match country.as_str() {
+            "FI" => FINLAND,
+            _ => return Err(format!("Unknown holiday country: {}. Use FI", country)),
+        };
+        Ok(HolidayCalendar { country, rules })
+    }
+
+    pub fn country(&self) -> &str {
+        &self.country
+    }
+
+    // holidays of the year as (date, name) in date order
+    pub fn holidays_in(&self, year: i32) -> Vec<(NaiveDate, &'static str)> {
+        let mut holidays: Vec<(NaiveDate, &'static str)> = self.rules.iter().filter_map(|(name, rule)| Some((rule.date_in(year)?, *name))).collect();
+        holidays.sort();
+        holidays
+    }
+
+    pub fn is_holiday(&self, date: NaiveDate) -> bool {
+        self.rules.iter().any(|(_, rule)| rule.date_in(date.year()) == Some(date))
+    }
+
+    // holidays from start to end, both included, as virtual events in category holiday/<country>
+    pub fn events_between(&self, start: NaiveDate, end: NaiveDate) -> Vec<Event> {
+        let category = vec![HOLIDAY_CATEGORY.to_string(), self.country.to_lowercase()];
+        (start.year()..=end.year())
+            .flat_map(|year| self.holidays_in(year))
+            .filter(|(date, _)| *date >= start && *date <= end)
+            .map(|(date, name)| Event::new(date, name.to_string(), category.clone()))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("Valid date")
+    }
+
+    #[test]
+    fn test_easter_and_rules() {
+        assert_eq!(easter_sunday(2024), Some(date(2024, 3, 31)));
+        assert_eq!(easter_sunday(2025), Some(date(2025, 4, 20)));
+        assert_eq!(easter_sunday(2038), Some(date(2038, 4, 25)));
+        // midsummer eve is friday between 19th and 25th of june
+        assert_eq!(Rule::WeekdayFrom(6, 19, Weekday::Fri).date_in(2024), Some(date(2024, 6, 21)));
+        assert_eq!(Rule::WeekdayFrom(6, 19, Weekday::Fri).date_in(2026), Some(date(2026, 6, 19)));
+        assert_eq!(Rule::WeekdayFrom(10, 31, Weekday::Sat).date_in(2026), Some(date(2026, 10, 31)));
+    }
+
+    #[test]
+    fn test_finnish_calendar() {
+        let calendar = HolidayCalendar::new("fi").unwrap();
+        let holidays = calendar.holidays_in(2024);
+        assert_eq!(holidays.len(), 15);
+        assert_eq!(holidays[2], (date(2024, 3, 29), "Good Friday"));
+        assert!(calendar.is_holiday(date(2024, 5, 9)) && !calendar.is_holiday(date(2024, 5, 10)));
+        let events = calendar.events_between(date(2023, 12, 24), date(2024, 1, 1));
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[3].category(), ["holiday", "fi"]);
+        assert!(HolidayCalendar::new("SE").is_err());
+    }
+}
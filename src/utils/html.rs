@@ -0,0 +1,253 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use chrono::Datelike;
+use crate::utils::all_utils::{stable_hash, Event, StringFormat};
+use crate::utils::svg::{escape_xml, render_svg};
+use crate::utils::timezone::ViewZone;
+
+// style shared by all pages, inlined so every page works on its own
+const STYLE: &str = "body { font-family: sans-serif; max-width: 60em; margin: 2em auto; padding: 0 1em; color: #222; }
+a { color: #1f5fa8; text-decoration: none; }
+h2 { border-bottom: 1px solid #ccc; }
+ul.events { list-style: none; padding-left: 0; }
+ul.events li { margin: 0.2em 0; }
+.date { font-family: monospace; margin-right: 0.5em; }
+.category { color: #666; font-size: 0.9em; margin-left: 0.5em; }
+.permalink { color: #bbb; margin-left: 0.3em; }
+li:target { background: #fff3b0; }
+#search { width: 100%; padding: 0.4em; font-size: 1em; }
+.timeline { overflow-x: auto; }";
+
+// search over the embedded index, matches every word against date, description and category
+const SEARCH_SCRIPT: &str = "const index = JSON.parse(document.getElementById('search-index').textContent);
+const input = document.getElementById('search');
+const results = document.getElementById('search-results');
+input.addEventListener('input', () => {
+  const words = input.value.toLowerCase().split(/\\s+/).filter(w => w);
+  results.innerHTML = '';
+  if (words.length === 0) return;
+  for (const e of index) {
+    const text = (e.date + ' ' + e.description + ' ' + e.category + ' ' + e.tags).toLowerCase();
+    if (!words.every(w => text.includes(w))) continue;
+    const li = document.createElement('li');
+    const a = document.createElement('a');
+    a.href = e.url;
+    a.textContent = e.date + ' ' + e.description;
+    li.appendChild(a);
+    results.appendChild(li);
+  }
+});";
+
+// escape text for a json string, "</" is escaped so the json can be inside a script element
+pub fn escape_json(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '/' => escaped.push_str("\\/"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", c as u32);
+            }
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+// file name of the category page, for example "category-microsoft-dotnet.html". Levels with other
+// than lowercase letters and digits get a hash of the path, so "a-b" and "a/b" are different pages
+pub fn category_page(category: &[String]) -> String {
+    let slug: String = category
+        .join("-")
+        .to_lowercase()
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '-' })
+        .collect();
+    let lossless = category.iter().all(|level| !level.is_empty() && level.chars().all(|c| c.is_alphanumeric() && !c.is_uppercase()));
+    if lossless {
+        format!("category-{}.html", slug)
+    } else {
+        format!("category-{}-{:08x}.html", slug, stable_hash(&category.join("/")))
+    }
+}
+
+// anchor ids of the sorted events, duplicates of an event get the number of the copy after the id
+fn anchor_ids<'a>(events: &[&'a Event]) -> Vec<(&'a Event, String)> {
+    let mut seen: BTreeMap<String, usize> = BTreeMap::new();
+    events
+        .iter()
+        .map(|event| {
+            let id = event.id();
+            let copies = seen.entry(id.clone()).or_insert(0);
+            *copies += 1;
+            let anchor = if *copies == 1 { id } else { format!("{}-{}", id, copies) };
+            (*event, anchor)
+        })
+        .collect()
+}
+
+fn page(title: &str, body: &str) -> String {
+    format!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>{}</title>\n<style>\n{}\n</style>\n</head>\n<body>\n{}</body>\n</html>\n",
+        escape_xml(title),
+        STYLE,
+        body
+    )
+}
+
+// one event as list item with permalink anchor by event id
+fn event_item(event: &Event, id: &str, zone: &ViewZone) -> String {
+    let category = if event.category().is_empty() {
+        String::new()
+    } else {
+        format!(
+            "<a class=\"category\" href=\"{}\">{}</a>",
+            category_page(event.category()),
+            escape_xml(&event.format_category(StringFormat::Print))
+        )
+    };
+    let tags = event.format_tags(&StringFormat::Print);
+    let tags = if tags.is_empty() { String::new() } else { format!(" <span class=\"category\">{}</span>", escape_xml(&tags)) };
+    format!(
+        "<li id=\"{}\"><span class=\"date\">{}</span>{}{}{}<a class=\"permalink\" href=\"#{}\">¶</a></li>\n",
+        id,
+        event.format_date(&StringFormat::Print, zone),
+        escape_xml(event.description()),
+        category,
+        tags,
+        id
+    )
+}
+
+// events grouped by year, latest year first
+fn events_by_year(events: &[&(&Event, String)], zone: &ViewZone) -> String {
+    let mut years: BTreeMap<i32, Vec<&(&Event, String)>> = BTreeMap::new();
+    for event in events {
+        years.entry(event.0.date_in(zone).year()).or_default().push(event);
+    }
+    let mut body = String::new();
+    for (year, year_events) in years.iter().rev() {
+        let _ = writeln!(body, "<h2 id=\"year-{}\">{} <span class=\"category\">{} events</span></h2>", year, year, year_events.len());
+        body.push_str("<ul class=\"events\">\n");
+        for (event, id) in year_events {
+            body.push_str(&event_item(event, id, zone));
+        }
+        body.push_str("</ul>\n");
+    }
+    body
+}
+
+// years that have events, oldest first
+fn event_years(events: &[&Event], zone: &ViewZone) -> Vec<i32> {
+    let mut years: Vec<i32> = events.iter().map(|event| event.date_in(zone).year()).collect();
+    years.dedup();
+    years
+}
+
+// json index of all events for the search box
+fn search_index(events: &[(&Event, String)], zone: &ViewZone) -> String {
+    let entries: Vec<String> = events
+        .iter()
+        .map(|(event, id)| {
+            format!(
+                "{{\"date\":\"{}\",\"description\":\"{}\",\"category\":\"{}\",\"tags\":\"{}\",\"url\":\"index.html#{}\"}}",
+                event.format_date(&StringFormat::Print, zone),
+                escape_json(event.description()),
+                escape_json(&event.format_category(StringFormat::Csv)),
+                escape_json(&event.format_tags(&StringFormat::Print)),
+                id
+            )
+        })
+        .collect();
+    format!("[{}]", entries.join(",\n"))
+}
+
+// generate static site as (file name, content) pairs: index.html with timeline, all events by year
+// and a search box, and one page for every category and its parent categories
+pub fn render_site(events: &[Event], zone: &ViewZone) -> Vec<(String, String)> {
+    let timeline = render_svg(events, zone);
+    let mut events: Vec<&Event> = events.iter().collect();
+    events.sort();
+    let anchored = anchor_ids(&events);
+
+    // pages of categories on every level, so "microsoft" lists also "microsoft/dotnet" events
+    let mut categories: BTreeMap<Vec<String>, Vec<&(&Event, String)>> = BTreeMap::new();
+    for event in &anchored {
+        for depth in 1..=event.0.category().len() {
+            categories.entry(event.0.category()[..depth].to_vec()).or_default().push(event);
+        }
+    }
+
+    let mut index = String::from("<h1>Events</h1>\n");
+    index.push_str("<input id=\"search\" type=\"search\" placeholder=\"Search events\" autocomplete=\"off\">\n<ul id=\"search-results\" class=\"events\"></ul>\n");
+    let year_links: Vec<String> = event_years(&events, zone).iter().rev().map(|year| format!("<a href=\"#year-{}\">{}</a>", year, year)).collect();
+    let _ = writeln!(index, "<p>{}</p>", year_links.join(" · "));
+    let category_links: Vec<String> = categories
+        .keys()
+        .map(|category| format!("<a href=\"{}\">{}</a>", category_page(category), escape_xml(&category.join("/"))))
+        .collect();
+    let _ = writeln!(index, "<p>Categories: {}</p>", category_links.join(" · "));
+    let _ = writeln!(index, "<div class=\"timeline\">\n{}</div>", timeline);
+    index.push_str(&events_by_year(&anchored.iter().collect::<Vec<_>>(), zone));
+    let _ = writeln!(index, "<script type=\"application/json\" id=\"search-index\">{}</script>", search_index(&anchored, zone));
+    let _ = writeln!(index, "<script>\n{}\n</script>", SEARCH_SCRIPT);
+
+    let mut pages = vec![("index.html".to_string(), page("Events", &index))];
+    for (category, category_events) in &categories {
+        let title = category.join("/");
+        let body = format!("<p><a href=\"index.html\">All events</a></p>\n<h1>{}</h1>\n{}", escape_xml(&title), events_by_year(category_events, zone));
+        pages.push((category_page(category), page(&title, &body)));
+    }
+    pages
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn event(year: i32, description: &str, category: &[&str]) -> Event {
+        let date = NaiveDate::from_ymd_opt(year, 3, 9).expect("Valid date");
+        Event::new(date, description.to_string(), category.iter().map(|level| level.to_string()).collect())
+    }
+
+    #[test]
+    fn test_render_site_pages_and_anchors() {
+        let events = vec![event(2022, ".NET 7 released", &["microsoft", "dotnet"]), event(2023, "Rust <1.68>", &["rust"])];
+        let pages = render_site(&events, &ViewZone::Local);
+        let names: Vec<&str> = pages.iter().map(|(name, _)| name.as_str()).collect();
+
+        assert_eq!(names, vec!["index.html", "category-microsoft.html", "category-microsoft-dotnet.html", "category-rust.html"]);
+        let index = &pages[0].1;
+        let id = events[1].id();
+        assert!(index.contains(&format!("<li id=\"{}\">", id)));
+        assert!(index.contains(&format!("href=\"#{}\"", id)));
+        assert!(index.contains("Rust &lt;1.68&gt;"));
+        // latest year first
+        assert!(index.find("year-2023\"").unwrap() < index.find("year-2022\"").unwrap());
+    }
+
+    #[test]
+    fn test_unique_category_pages_and_anchors() {
+        let page = |category: &[&str]| category_page(&category.iter().map(|level| level.to_string()).collect::<Vec<String>>());
+        assert_eq!(page(&["rust", "2024"]), "category-rust-2024.html");
+        assert_ne!(page(&["a-b"]), page(&["a", "b"]));
+        assert_ne!(page(&["c++"]), page(&["c--"]));
+
+        let events = vec![event(2023, "Duplicate", &["rust"]), event(2023, "Duplicate", &["rust"])];
+        let index = &render_site(&events, &ViewZone::Local)[0].1;
+        let id = events[0].id();
+        assert_eq!(index.matches(&format!("<li id=\"{}\">", id)).count(), 1);
+        assert!(index.contains(&format!("<li id=\"{}-2\">", id)));
+        assert!(index.contains(&format!("index.html#{}-2", id)));
+    }
+
+    #[test]
+    fn test_escape_json_inside_script() {
+        assert_eq!(escape_json("say \"hi\"</script>\n"), "say \\\"hi\\\"<\\/script>\\n");
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error as StdError;
+use std::fs::File;
+use std::path::Path;
+use std::sync::OnceLock;
+use csv::{Error, ReaderBuilder, WriterBuilder};
+use rust_stemmers::{Algorithm, Stemmer};
+use crate::utils::all_utils::Event;
+use crate::utils::normalize::normalize;
+use crate::utils::search::match_typos;
+
+// split text to case folded words without accents after NFKC normalization, so "ﬁ" is "fi", full width
+// digits are digits and "näyttö" is "naytto" like in search
+pub fn tokenize(text: &str) -> Vec<String> {
+    normalize(text, false, true)
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|token| !token.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+// stemmers are made once, stems are asked for every word of every event
+static STEMMERS: OnceLock<[Stemmer; 2]> = OnceLock::new();
+
+// finnish and english stems of the token, language of the events is not known
+pub fn stems(token: &str) -> Vec<String> {
+    let stemmers = STEMMERS.get_or_init(|| [Stemmer::create(Algorithm::Finnish), Stemmer::create(Algorithm::English)]);
+    let mut stems: Vec<String> = stemmers.iter().map(|stemmer| stemmer.stem(token).into_owned()).collect();
+    stems.sort();
+    stems.dedup();
+    stems
+}
+
+// words of the event that are indexed: description, category levels and tags
+fn event_tokens(event: &Event) -> BTreeSet<String> {
+    let text = format!("{} {} {}", event.description(), event.category().join(" "), event.tags().join(" "));
+    tokenize(&text).into_iter().collect()
+}
+
+// inverted index from words and their stems to ids of the events having them
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SearchIndex {
+    postings: BTreeMap<String, BTreeSet<String>>,
+}
+
+impl SearchIndex {
+    pub fn build(events: &[Event]) -> Self {
+        let mut index = SearchIndex::default();
+        for event in events {
+            index.add_event(event);
+        }
+        index
+    }
+
+    // words are indexed as they are and as stems, so typos and inflected forms can be found
+    pub fn add_event(&mut self, event: &Event) {
+        let id = event.id();
+        for token in event_tokens(event) {
+            for term in stems(&token).into_iter().chain([token]) {
+                self.postings.entry(term).or_default().insert(id.clone());
+            }
+        }
+    }
+
+    // remove events from the index. Remaining events with the same id as a removed one are added back
+    pub fn remove_events(&mut self, removed: &[Event], remaining: &[Event]) {
+        let removed_ids: BTreeSet<String> = removed.iter().map(Event::id).collect();
+        self.postings.retain(|_, ids| {
+            ids.retain(|id| !removed_ids.contains(id));
+            !ids.is_empty()
+        });
+        for event in remaining.iter().filter(|event| removed_ids.contains(&event.id())) {
+            self.add_event(event);
+        }
+    }
+
+    // ids of events having the word, its stems or words starting with it, looked up from the sorted terms
+    fn lookup(&self, token: &str) -> BTreeSet<String> {
+        let mut ids = BTreeSet::new();
+        for (_, term_ids) in self.postings.range(token.to_string()..).take_while(|(term, _)| term.starts_with(token)) {
+            ids.extend(term_ids.iter().cloned());
+        }
+        for stem in stems(token) {
+            if let Some(term_ids) = self.postings.get(&stem) {
+                ids.extend(term_ids.iter().cloned());
+            }
+        }
+        ids
+    }
+
+    // ids of events having a word that has the token inside it or matches it with typos, like search.
+    // Terms already found by lookup and terms too short to match are skipped
+    fn scan(&self, token: &str, found: &BTreeSet<String>) -> BTreeSet<String> {
+        let token_chars: Vec<char> = token.chars().collect();
+        let min_length = token_chars.len().saturating_sub(2).max(1);
+        let mut ids = BTreeSet::new();
+        for (term, term_ids) in &self.postings {
+            if term_ids.is_subset(found) || term_ids.is_subset(&ids) {
+                continue;
+            }
+            let term_chars: Vec<char> = term.chars().collect();
+            if term_chars.len() >= min_length && (term.contains(token) || match_typos(&token_chars, &term_chars).is_some()) {
+                ids.extend(term_ids.iter().cloned());
+            }
+        }
+        ids
+    }
+
+    // ids of events that may match every query word, the same events search finds and possibly more.
+    // Words, their stems and prefixes are looked up directly, matches inside words and typos are scanned
+    pub fn candidates(&self, query: &str) -> BTreeSet<String> {
+        let mut candidates: Option<BTreeSet<String>> = None;
+        for token in tokenize(query) {
+            let mut word_ids = self.lookup(&token);
+            let scanned = self.scan(&token, &word_ids);
+            word_ids.extend(scanned);
+            candidates = Some(match candidates {
+                Some(candidates) => candidates.intersection(&word_ids).cloned().collect(),
+                None => word_ids,
+            });
+        }
+        candidates.unwrap_or_default()
+    }
+
+    // read index from csv with columns term,ids. Missing file gives None, the index is optional
+    pub fn read(file_path: &str) -> Result<Option<Self>, Error> {
+        if !Path::new(file_path).exists() {
+            return Ok(None);
+        }
+        let file = File::open(file_path)?;
+        let mut rdr = ReaderBuilder::new().flexible(true).from_reader(file);
+        let mut index = SearchIndex::default();
+        for result in rdr.records() {
+            let record = result?;
+            let ids = record.get(1).unwrap_or_default().split_whitespace().map(String::from).collect();
+            index.postings.insert(record.get(0).unwrap_or_default().to_string(), ids);
+        }
+        Ok(Some(index))
+    }
+
+    // write index over the file
+    pub fn write(&self, file_path: &str) -> Result<(), Box<dyn StdError>> {
+        let mut wtr = WriterBuilder::new().from_path(file_path)?;
+        wtr.write_record(["term", "ids"])?;
+        for (term, ids) in &self.postings {
+            wtr.write_record([term.as_str(), &ids.iter().cloned().collect::<Vec<_>>().join(" ")])?;
+        }
+        wtr.flush()?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::search::search_events;
+    use chrono::NaiveDate;
+
+    fn event(description: &str, category: &str) -> Event {
+        let date = NaiveDate::from_ymd_opt(2023, 11, 14).expect("Valid date");
+        Event::new(date, description.to_string(), vec![category.to_string()])
+    }
+
+    #[test]
+    fn test_tokenize_normalizes_and_stems() {
+        assert_eq!(tokenize("ﬁle: Ｒust 1.70, Näyttö"), vec!["file", "rust", "1", "70", "naytto"]);
+        // finnish inflection and english plural find the same stem
+        assert!(stems("talossa").contains(&"talo".to_string()));
+        assert!(stems("releases").iter().any(|stem| stems("released").contains(stem)));
+    }
+
+    #[test]
+    fn test_index_candidates_and_incremental_updates() {
+        let events = vec![event("Uusi talo valmistui", "koti"), event(".NET 8 released", "microsoft")];
+        let mut index = SearchIndex::build(&events);
+
+        assert_eq!(index.candidates("talossa"), [events[0].id()].into_iter().collect());
+        assert_eq!(index.candidates("relased microsoft"), [events[1].id()].into_iter().collect());
+        assert!(index.candidates("talo microsoft").is_empty());
+
+        index.remove_events(&events[..1], &events[1..]);
+        assert!(index.candidates("talo").is_empty());
+        index.add_event(&events[0]);
+        assert_eq!(index, SearchIndex::build(&events));
+    }
+
+    #[test]
+    fn test_candidates_include_search_results() {
+        let events = vec![
+            event("macOS Sierra released", "apple"),
+            event("OS X Catalina", "apple"),
+            event("Escape from capital", "games"),
+            event("Uusi talo valmistui", "koti"),
+            event(".NET 8 released", "microsoft"),
+        ];
+        let index = SearchIndex::build(&events);
+        for query in ["os", "cap", "relased", "talossa", "catalna", "sierra apple", "net 8", "ape"] {
+            let candidates = index.candidates(query);
+            for hit in search_events(&events, query) {
+                assert!(candidates.contains(&hit.event.id()), "{} misses {}", query, hit.event.description());
+            }
+        }
+        // prefix match of "os" does not hide the match inside "macos"
+        assert!(index.candidates("os").contains(&events[0].id()));
+    }
+}
@@ -0,0 +1,139 @@
+use regex::{Regex, RegexBuilder};
+use crate::utils::normalize::normalize;
+
+// ways to match description and category filters in --match
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MatchMode {
+    #[default]
+    Prefix,
+    Contains,
+    Exact,
+    Regex,
+    Glob,
+}
+
+impl MatchMode {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        match input.trim().to_lowercase().as_str() {
+            "prefix" => Ok(MatchMode::Prefix),
+            "contains" => Ok(MatchMode::Contains),
+            "exact" => Ok(MatchMode::Exact),
+            "regex" => Ok(MatchMode::Regex),
+            "glob" => Ok(MatchMode::Glob),
+            other => Err(format!("Unknown match mode: {}. Use prefix, contains, exact, regex or glob", other)),
+        }
+    }
+}
+
+// how filter strings are matched, default is case insensitive prefix. Texts are always compared
+// NFKC normalized, accents are ignored if asked
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MatchOptions {
+    pub mode: MatchMode,
+    pub case_sensitive: bool,
+    pub ignore_accents: bool,
+}
+
+impl MatchOptions {
+    // text normalized for comparing with these options
+    pub fn normalize(&self, text: &str) -> String {
+        normalize(text, self.case_sensitive, self.ignore_accents)
+    }
+}
+
+// pattern prepared for matching many texts, regexes and globs are compiled once
+#[derive(Debug, Clone)]
+pub struct TextMatcher {
+    pattern: String,
+    options: MatchOptions,
+    regex: Option<Regex>,
+}
+
+impl TextMatcher {
+    pub fn new(pattern: &str, options: MatchOptions) -> Result<Self, String> {
+        // regex is not case folded, "\\D" is not "\\d". Its case is ignored by the regex instead
+        let regex = match options.mode {
+            MatchMode::Regex => Some(normalize(pattern, true, options.ignore_accents)),
+            MatchMode::Glob => Some(glob_to_regex(&normalize(pattern, true, options.ignore_accents))),
+            _ => None,
+        };
+        let regex = match regex {
+            Some(regex) => Some(
+                RegexBuilder::new(&regex)
+                    .case_insensitive(!options.case_sensitive)
+                    .build()
+                    .map_err(|err| format!("Invalid pattern {}: {}", pattern, err))?,
+            ),
+            None => None,
+        };
+        let pattern = options.normalize(pattern);
+        Ok(TextMatcher { pattern, options, regex })
+    }
+
+    pub fn is_match(&self, text: &str) -> bool {
+        let text = self.options.normalize(text);
+        if let Some(regex) = &self.regex {
+            return regex.is_match(&text);
+        }
+        match self.options.mode {
+            MatchMode::Contains => text.contains(&self.pattern),
+            MatchMode::Exact => text == self.pattern,
+            _ => text.starts_with(&self.pattern),
+        }
+    }
+}
+
+// glob to anchored regex, '*' is any text and '?' any one character
+fn glob_to_regex(glob: &str) -> String {
+    let mut regex = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => regex.push_str(".*"),
+            '?' => regex.push('.'),
+            c => regex.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    regex.push('$');
+    regex
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn matcher(pattern: &str, mode: MatchMode, case_sensitive: bool) -> TextMatcher {
+        TextMatcher::new(pattern, MatchOptions { mode, case_sensitive, ignore_accents: false }).unwrap()
+    }
+
+    #[test]
+    fn test_text_matcher_modes() {
+        let text = "macOS 11 Big Sur released";
+        assert!(matcher("MACOS", MatchMode::Prefix, false).is_match(text));
+        assert!(!matcher("Big Sur", MatchMode::Prefix, false).is_match(text));
+        assert!(matcher("big sur", MatchMode::Contains, false).is_match(text));
+        assert!(!matcher("macOS 11", MatchMode::Exact, false).is_match(text));
+        assert!(matcher(r"^macos \d+ big", MatchMode::Regex, false).is_match(text));
+        assert!(matcher("macos ?? * released", MatchMode::Glob, false).is_match(text));
+        assert!(!matcher("macos*", MatchMode::Glob, false).is_match("old macos"));
+    }
+
+    #[test]
+    fn test_text_matcher_case_sensitive_and_errors() {
+        assert!(!matcher("big sur", MatchMode::Contains, true).is_match("macOS 11 Big Sur released"));
+        assert!(matcher("Big Sur", MatchMode::Contains, true).is_match("macOS 11 Big Sur released"));
+        assert!(!matcher("big", MatchMode::Regex, true).is_match("Big"));
+        assert!(TextMatcher::new("(unclosed", MatchOptions { mode: MatchMode::Regex, ..MatchOptions::default() }).is_err());
+        assert!(matcher("a.b", MatchMode::Glob, false).is_match("A.B") && !matcher("a.b", MatchMode::Glob, false).is_match("axb"));
+    }
+
+    #[test]
+    fn test_text_matcher_normalization_and_accents() {
+        // NFD form of the text matches NFC pattern
+        assert!(matcher("näyttö", MatchMode::Prefix, false).is_match("NA\u{308}ytto\u{308} vaihdettu"));
+        assert!(!matcher("naytto", MatchMode::Prefix, false).is_match("Näyttö vaihdettu"));
+        let options = MatchOptions { mode: MatchMode::Contains, ignore_accents: true, ..MatchOptions::default() };
+        assert!(TextMatcher::new("naytto", options).unwrap().is_match("Uusi näyttö"));
+        let options = MatchOptions { mode: MatchMode::Regex, ignore_accents: true, ..MatchOptions::default() };
+        assert!(TextMatcher::new(r"^UUSI \w+o$", options).unwrap().is_match("Uusi näyttö"));
+    }
+}
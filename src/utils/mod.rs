@@ -1 +1,26 @@
-pub mod all_utils;
\ No newline at end of file
+pub mod agenda;
+pub mod all_utils;
+pub mod calendar;
+pub mod categories;
+pub mod charts;
+pub mod color;
+pub mod dedupe;
+pub mod group;
+pub mod history;
+pub mod holidays;
+pub mod html;
+pub mod index;
+pub mod matcher;
+pub mod normalize;
+pub mod outline;
+pub mod pager;
+pub mod registry;
+pub mod relative;
+pub mod search;
+pub mod sort;
+pub mod stats;
+pub mod svg;
+pub mod table;
+pub mod tags;
+pub mod template;
+pub mod timezone;
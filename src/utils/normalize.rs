@@ -0,0 +1,71 @@
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+// lowercase with the full case foldings lowercase misses, so "Straße" and "STRASSE" are equal
+fn case_fold(text: &str) -> String {
+    let mut folded = String::new();
+    for c in text.chars() {
+        match c {
+            'ß' | 'ẞ' => folded.push_str("ss"),
+            'ς' => folded.push('σ'),
+            'ſ' => folded.push('s'),
+            c => folded.extend(c.to_lowercase()),
+        }
+    }
+    folded
+}
+
+// remove accents and other combining marks, "Näyttö" -> "Naytto"
+pub fn strip_accents(text: &str) -> String {
+    text.nfd().filter(|c| !is_combining_mark(*c)).nfc().collect()
+}
+
+// NFKC normalized text for comparing, case folded unless case sensitive and without accents if asked.
+// NFC and NFD forms of "ä" and ligatures like "ﬁ" compare equal after this
+pub fn normalize(text: &str, case_sensitive: bool, ignore_accents: bool) -> String {
+    let text: String = text.nfkc().collect();
+    let text = if case_sensitive { text } else { case_fold(&text) };
+    if ignore_accents {
+        strip_accents(&text)
+    } else {
+        text
+    }
+}
+
+// normalized and case folded text, the default for comparing strings
+pub fn fold(text: &str) -> String {
+    normalize(text, false, false)
+}
+
+// folded levels of a category path
+pub fn fold_path(path: &[String]) -> Vec<String> {
+    path.iter().map(|level| fold(level)).collect()
+}
+
+// single character folded and without accents, for comparing texts char by char
+pub fn fold_char(c: char) -> char {
+    let base = c.nfd().next().unwrap_or(c);
+    base.to_lowercase().next().unwrap_or(base)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_forms_case_and_accents() {
+        // "ä" as one char and as "a" with combining diaeresis
+        assert_eq!(fold("N\u{e4}ytt\u{f6}"), fold("NA\u{308}ytto\u{308}"));
+        assert_eq!(fold("ﬁle STRASSE"), fold("File straße"));
+        assert_eq!(normalize("Näyttö", true, false), "Näyttö");
+        assert_eq!(normalize("Näyttö", false, true), "naytto");
+        assert_ne!(fold("Näyttö"), fold("Naytto"));
+    }
+
+    #[test]
+    fn test_fold_char() {
+        assert_eq!(fold_char('Ä'), 'a');
+        assert_eq!(fold_char('é'), 'e');
+        assert_eq!(fold_char('X'), 'x');
+    }
+}
@@ -0,0 +1,126 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use chrono::{Datelike, NaiveDate};
+use crate::utils::all_utils::Event;
+use crate::utils::timezone::ViewZone;
+
+// events grouped by year and month as seen in the zone, oldest first
+fn group_by_month<'a>(events: &'a [Event], zone: &ViewZone) -> BTreeMap<(i32, u32), Vec<&'a Event>> {
+    let mut events: Vec<&Event> = events.iter().collect();
+    events.sort();
+    let mut months: BTreeMap<(i32, u32), Vec<&Event>> = BTreeMap::new();
+    for event in events {
+        let date = event.date_in(zone);
+        months.entry((date.year(), date.month())).or_default().push(event);
+    }
+    months
+}
+
+fn month_name(year: i32, month: u32) -> String {
+    NaiveDate::from_ymd_opt(year, month, 1).map(|date| date.format("%B").to_string()).unwrap_or_default()
+}
+
+// escape characters that markdown would read as formatting
+fn escape_markdown(text: &str) -> String {
+    let mut escaped = String::new();
+    for c in text.chars() {
+        if matches!(c, '\\' | '*' | '_' | '`' | '[' | ']' | '<' | '>' | '#') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+// org tags may only have letters, numbers, '_', '@', '#' and '%'
+fn org_tag(text: &str) -> String {
+    text.chars().map(|c| if c.is_alphanumeric() || matches!(c, '_' | '@' | '#' | '%') { c } else { '_' }).collect()
+}
+
+// markdown document with year and month headings and events as bullets, category and tags as #tags
+pub fn render_markdown(events: &[Event], zone: &ViewZone) -> String {
+    let mut output = String::from("# Events\n");
+    let mut current_year = None;
+    for ((year, month), month_events) in group_by_month(events, zone) {
+        if current_year != Some(year) {
+            let _ = write!(output, "\n## {}\n", year);
+            current_year = Some(year);
+        }
+        let _ = write!(output, "\n### {}\n\n", month_name(year, month));
+        for event in month_events {
+            let mut line = format!("- **{}**", event.date_in(zone).format("%Y-%m-%d"));
+            if let Some(time) = event.time() {
+                let _ = write!(line, " {}", zone.format(time, "%H:%M"));
+            }
+            let _ = write!(line, " {}", escape_markdown(event.description()));
+            if !event.category().is_empty() {
+                let _ = write!(line, " `#{}`", event.category().join("/"));
+            }
+            for tag in event.tags() {
+                let _ = write!(line, " `#{}`", tag);
+            }
+            let _ = writeln!(output, "{}", line);
+        }
+    }
+    output
+}
+
+// org document with year and month headings and events as headings with active timestamps so they
+// show in org-agenda. Category levels and tags are org tags
+pub fn render_org(events: &[Event], zone: &ViewZone) -> String {
+    let mut output = String::from("#+TITLE: Events\n");
+    let mut current_year = None;
+    for ((year, month), month_events) in group_by_month(events, zone) {
+        if current_year != Some(year) {
+            let _ = writeln!(output, "* {}", year);
+            current_year = Some(year);
+        }
+        let _ = writeln!(output, "** {}", month_name(year, month));
+        for event in month_events {
+            let timestamp = match event.time() {
+                Some(time) => zone.format(time, "<%Y-%m-%d %a %H:%M>"),
+                None => event.date_in(zone).format("<%Y-%m-%d %a>").to_string(),
+            };
+            let tags: Vec<String> = event.category().iter().chain(event.tags()).map(|tag| org_tag(tag)).collect();
+            let tags = if tags.is_empty() { String::new() } else { format!(" :{}:", tags.join(":")) };
+            let _ = writeln!(output, "*** {}{}", event.description(), tags);
+            let _ = writeln!(output, "{}", timestamp);
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_events() -> Vec<Event> {
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).expect("Valid date");
+        vec![
+            Event::new(date(2023, 11, 14), ".NET 8 released".to_string(), vec!["microsoft".to_string(), "dotnet".to_string()])
+                .with_tags(vec!["lts".to_string()]),
+            Event::new(date(2023, 3, 9), "Rust 1.68 *released*".to_string(), vec!["rust".to_string()]),
+            Event::new(date(2024, 1, 1), "New year".to_string(), Vec::new()),
+        ]
+    }
+
+    #[test]
+    fn test_render_markdown_groups_by_year_and_month() {
+        let markdown = render_markdown(&create_test_events(), &ViewZone::Local);
+
+        assert_eq!(
+            markdown,
+            "# Events\n\n## 2023\n\n### March\n\n- **2023-03-09** Rust 1.68 \\*released\\* `#rust`\n\n### November\n\n\
+             - **2023-11-14** .NET 8 released `#microsoft/dotnet` `#lts`\n\n## 2024\n\n### January\n\n- **2024-01-01** New year\n"
+        );
+    }
+
+    #[test]
+    fn test_render_org_timestamps_and_tags() {
+        let org = render_org(&create_test_events(), &ViewZone::Local);
+
+        assert!(org.starts_with("#+TITLE: Events\n* 2023\n** March\n*** Rust 1.68 *released* :rust:\n<2023-03-09 Thu>\n"));
+        assert!(org.contains("*** .NET 8 released :microsoft:dotnet:lts:\n<2023-11-14 Tue>\n"));
+        assert!(org.contains("* 2024\n** January\n*** New year\n<2024-01-01 Mon>\n"));
+    }
+}
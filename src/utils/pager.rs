@@ -0,0 +1,51 @@
+use std::io::{IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+// pager used when PAGER is not set, -R shows colors
+const DEFAULT_PAGER: &str = "less -R";
+
+// height of the terminal if stdout is one
+pub fn terminal_height() -> Option<usize> {
+    if !std::io::stdout().is_terminal() {
+        return None;
+    }
+    terminal_size::terminal_size().map(|(_, terminal_size::Height(height))| usize::from(height))
+}
+
+// true if lines do not fit on the screen, leaving a line for the prompt
+pub fn needs_pager(line_count: usize, height: Option<usize>) -> bool {
+    height.is_some_and(|height| line_count >= height)
+}
+
+// print lines, through PAGER if they dont fit in the terminal. If pager cannot be started, print directly
+pub fn print_paged(lines: &[String]) {
+    if needs_pager(lines.len(), terminal_height()) {
+        let pager = std::env::var("PAGER").ok().filter(|pager| !pager.trim().is_empty()).unwrap_or_else(|| DEFAULT_PAGER.to_string());
+        let mut parts = pager.split_whitespace();
+        if let Some(program) = parts.next() {
+            if let Ok(mut child) = Command::new(program).args(parts).stdin(Stdio::piped()).spawn() {
+                if let Some(mut stdin) = child.stdin.take() {
+                    // pager may be closed before all lines are written
+                    let _ = stdin.write_all(format!("{}\n", lines.join("\n")).as_bytes());
+                }
+                let _ = child.wait();
+                return;
+            }
+        }
+    }
+    for line in lines {
+        println!("{}", line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_needs_pager() {
+        assert!(!needs_pager(10, None));
+        assert!(!needs_pager(10, Some(24)));
+        assert!(needs_pager(24, Some(24)));
+    }
+}
@@ -0,0 +1,176 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::path::Path;
+use csv::{Error, ReaderBuilder};
+use crate::utils::all_utils::parse_category;
+use crate::utils::normalize::{fold, fold_path};
+
+// one declared category in the categories file
+#[derive(Debug, Clone, PartialEq)]
+pub struct CategoryInfo {
+    pub path: Vec<String>,
+    pub description: String,
+    pub color: String,
+}
+
+// known categories and their aliases read from the optional categories file.
+// file has columns: category,aliases,description,color
+// for example: vendor/microsoft,ms msft,Microsoft products,blue
+#[derive(Debug, Default)]
+pub struct CategoryRegistry {
+    categories: Vec<CategoryInfo>,
+    aliases: BTreeMap<String, Vec<String>>,
+}
+
+impl CategoryRegistry {
+    // read registry from csv, missing file gives empty registry that accepts everything
+    pub fn read(file_path: &str) -> Result<Self, Error> {
+        let mut registry = CategoryRegistry::default();
+        if !Path::new(file_path).exists() {
+            return Ok(registry);
+        }
+        let file = File::open(file_path)?;
+        let mut rdr = ReaderBuilder::new().flexible(true).from_reader(file);
+
+        for result in rdr.records() {
+            // go through readings, note about error-lines, but still continue
+            let record = match result {
+                Ok(record) => record,
+                Err(err) => {
+                    eprintln!("Error reading categories record: {}", err);
+                    continue;
+                }
+            };
+            let path = match parse_category(&record.get(0).unwrap_or_default().to_lowercase(), '/') {
+                Ok(path) if !path.is_empty() => path,
+                Ok(_) => continue,
+                Err(err) => {
+                    eprintln!("Invalid category in categories file: {}", err);
+                    continue;
+                }
+            };
+            for alias in record.get(1).unwrap_or_default().split_whitespace() {
+                registry.aliases.insert(fold(alias), path.clone());
+            }
+            registry.categories.push(CategoryInfo {
+                path,
+                description: record.get(2).unwrap_or_default().trim().to_string(),
+                color: record.get(3).unwrap_or_default().trim().to_string(),
+            });
+        }
+        Ok(registry)
+    }
+
+    // add category to registry, used when registry is built in code
+    pub fn declare(&mut self, path: Vec<String>, aliases: &[&str], description: &str, color: &str) {
+        for alias in aliases {
+            self.aliases.insert(fold(alias), path.clone());
+        }
+        self.categories.push(CategoryInfo { path, description: description.to_string(), color: color.to_string() });
+    }
+
+    // true if no categories are declared, then all categories are accepted
+    pub fn is_empty(&self) -> bool {
+        self.categories.is_empty()
+    }
+
+    // all declared categories
+    pub fn categories(&self) -> &[CategoryInfo] {
+        &self.categories
+    }
+
+    // replace the longest alias at the start of the path, "ms/dotnet" -> "microsoft/dotnet"
+    pub fn resolve(&self, category: &[String]) -> Vec<String> {
+        for length in (1..=category.len()).rev() {
+            if let Some(target) = self.aliases.get(&fold(&category[..length].join("/"))) {
+                let mut resolved = target.clone();
+                resolved.extend_from_slice(&category[length..]);
+                return resolved;
+            }
+        }
+        category.to_vec()
+    }
+
+    // category is known if it or some of its subcategories is declared
+    pub fn is_known(&self, category: &[String]) -> bool {
+        let category = fold_path(category);
+        self.is_empty() || category.is_empty() || self.categories.iter().any(|info| fold_path(&info.path).starts_with(&category))
+    }
+
+    // declared category with the exact path
+    pub fn info(&self, category: &[String]) -> Option<&CategoryInfo> {
+        let category = fold_path(category);
+        self.categories.iter().find(|info| fold_path(&info.path) == category)
+    }
+
+    // closest declared category or alias to given unknown category
+    pub fn suggest(&self, category: &[String]) -> Option<String> {
+        let wanted = category.join("/");
+        let candidates = self.categories.iter().map(|info| info.path.join("/")).chain(self.aliases.keys().cloned());
+        candidates
+            .map(|candidate| (edit_distance(&wanted, &candidate), candidate))
+            // suggest only if less than half of the name must change
+            .filter(|(distance, candidate)| distance * 2 <= candidate.chars().count().max(wanted.chars().count()))
+            .min()
+            .map(|(_, candidate)| candidate)
+    }
+}
+
+// levenshtein distance counted in chars
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b_chars: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b_chars.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b_chars.len() + 1];
+        for (j, b_char) in b_chars.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+    previous[b_chars.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(category: &str) -> Vec<String> {
+        parse_category(category, '/').expect("Valid category")
+    }
+
+    fn create_test_registry() -> CategoryRegistry {
+        let mut registry = CategoryRegistry::default();
+        registry.declare(path("microsoft"), &["ms", "msft"], "Microsoft products", "blue");
+        registry.declare(path("vendor/apple/macos"), &["mac"], "", "");
+        registry
+    }
+
+    #[test]
+    fn test_registry_resolves_aliases() {
+        let registry = create_test_registry();
+
+        assert_eq!(registry.resolve(&path("ms")), path("microsoft"));
+        assert_eq!(registry.resolve(&path("ms/dotnet")), path("microsoft/dotnet"));
+        assert_eq!(registry.resolve(&path("rust")), path("rust"));
+    }
+
+    #[test]
+    fn test_registry_known_and_suggestion() {
+        let registry = create_test_registry();
+
+        assert!(registry.is_known(&path("vendor/apple")));
+        assert!(!registry.is_known(&path("mircosoft")));
+        assert_eq!(registry.suggest(&path("mircosoft")), Some("microsoft".to_string()));
+        assert_eq!(registry.suggest(&path("java")), None);
+        // empty registry accepts everything
+        assert!(CategoryRegistry::default().is_known(&path("anything")));
+    }
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("catalina", "catalna"), 1);
+        assert_eq!(edit_distance("näyttö", "nayttö"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
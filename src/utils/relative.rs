@@ -0,0 +1,264 @@
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
+use crate::utils::all_utils::validate_date_format;
+use crate::utils::holidays::HolidayCalendar;
+
+// parse date given as YYYY-mm-dd or "today"
+pub fn parse_date(input: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    let input = input.trim();
+    if input.eq_ignore_ascii_case("today") {
+        return Ok(today);
+    }
+    let error = || format!("Invalid date: {}. Use format YYYY-mm-dd or today", input);
+    if !validate_date_format(input) {
+        return Err(error());
+    }
+    NaiveDate::parse_from_str(input, "%Y-%m-%d").map_err(|_| error())
+}
+
+// whole years, months and days from the earlier date to the later one. Months are counted like
+// calendar months, 2023-01-31 to 2023-02-28 is 28 days and to 2023-03-01 is 1 month 1 day
+pub fn calendar_diff(from: NaiveDate, to: NaiveDate) -> (u32, u32, u32) {
+    let (from, to) = if from <= to { (from, to) } else { (to, from) };
+    let mut months = (to.year() - from.year()) * 12 + to.month() as i32 - from.month() as i32;
+    if to.day() < from.day() {
+        months -= 1;
+    }
+    let months = months.max(0) as u32;
+    let anchor = from.checked_add_months(Months::new(months)).unwrap_or(from);
+    let days = (to - anchor).num_days().max(0) as u32;
+    (months / 12, months % 12, days)
+}
+
+fn plural(count: u32, unit: &str) -> String {
+    if count == 1 {
+        format!("1 {}", unit)
+    } else {
+        format!("{} {}s", count, unit)
+    }
+}
+
+// years, months and days as text with at most max_parts largest units, "3 years 2 months"
+pub fn format_units(years: u32, months: u32, days: u32, max_parts: usize) -> String {
+    let parts: Vec<String> = [(years, "year"), (months, "month"), (days, "day")]
+        .into_iter()
+        .skip_while(|(count, _)| *count == 0)
+        .take(max_parts)
+        .filter(|(count, _)| *count > 0)
+        .map(|(count, unit)| plural(count, unit))
+        .collect();
+    if parts.is_empty() {
+        "0 days".to_string()
+    } else {
+        parts.join(" ")
+    }
+}
+
+// date relative to today like "in 12 days", "3 years 2 months ago" or "tomorrow"
+pub fn format_relative(date: NaiveDate, today: NaiveDate) -> String {
+    match (date - today).num_days() {
+        0 => "today".to_string(),
+        1 => "tomorrow".to_string(),
+        -1 => "yesterday".to_string(),
+        days => {
+            let (years, months, day_count) = calendar_diff(today, date);
+            // near dates in days, later ones in the two largest calendar units
+            let text = if years == 0 && months == 0 { plural(day_count, "day") } else { format_units(years, months, day_count, 2) };
+            if days > 0 {
+                format!("in {}", text)
+            } else {
+                format!("{} ago", text)
+            }
+        }
+    }
+}
+
+// amount of time to move a date, parsed from texts like "14d", "+3w", "-2m", "1y" or "5b" for business days
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Offset {
+    Days(i64),
+    BusinessDays(i64),
+    Weeks(i64),
+    Months(i64),
+    Years(i64),
+}
+
+impl Offset {
+    // number is days if no unit is given
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let error = || format!("Invalid offset: {}. Use number with unit d, b, w, m or y, for example +3w", input);
+        let text = input.trim();
+        let (number, unit) = match text.char_indices().last() {
+            Some((index, c)) if c.is_ascii_alphabetic() => (&text[..index], c.to_ascii_lowercase()),
+            _ => (text, 'd'),
+        };
+        let count: i64 = number.strip_prefix('+').unwrap_or(number).parse().map_err(|_| error())?;
+        match unit {
+            'd' => Ok(Offset::Days(count)),
+            'b' => Ok(Offset::BusinessDays(count)),
+            'w' => Ok(Offset::Weeks(count)),
+            'm' => Ok(Offset::Months(count)),
+            'y' => Ok(Offset::Years(count)),
+            _ => Err(error()),
+        }
+    }
+
+    // move the date, business days skip only weekends
+    pub fn apply(&self, date: NaiveDate) -> Option<NaiveDate> {
+        self.apply_with(date, None)
+    }
+
+    // move the date, months and years end on the last day of a shorter month and business days skip
+    // weekends and holidays. None if out of range
+    pub fn apply_with(&self, date: NaiveDate, holidays: Option<&HolidayCalendar>) -> Option<NaiveDate> {
+        let add_months = |months: i64| {
+            let magnitude = Months::new(u32::try_from(months.unsigned_abs()).ok()?);
+            if months < 0 {
+                date.checked_sub_months(magnitude)
+            } else {
+                date.checked_add_months(magnitude)
+            }
+        };
+        match self {
+            Offset::Days(days) => date.checked_add_signed(Duration::try_days(*days)?),
+            Offset::BusinessDays(days) => {
+                let step = Duration::days(days.signum());
+                let mut moved = date;
+                for _ in 0..days.unsigned_abs() {
+                    moved = moved.checked_add_signed(step)?;
+                    while !is_business_day(moved, holidays) {
+                        moved = moved.checked_add_signed(step)?;
+                    }
+                }
+                Some(moved)
+            }
+            Offset::Weeks(weeks) => date.checked_add_signed(Duration::try_weeks(*weeks)?),
+            Offset::Months(months) => add_months(*months),
+            Offset::Years(years) => add_months(years.checked_mul(12)?),
+        }
+    }
+}
+
+// date moved by the offset text, error if the offset is invalid or the date goes out of range
+pub fn offset_date(date: NaiveDate, offset: &str, holidays: Option<&HolidayCalendar>) -> Result<NaiveDate, String> {
+    let offset = Offset::parse(offset)?;
+    offset.apply_with(date, holidays).ok_or_else(|| "Error: Date is out of range.".to_string())
+}
+
+// difference of the dates as days, weeks, months, years and business days, negative if to is before from
+pub fn format_diff(from: NaiveDate, to: NaiveDate, holidays: Option<&HolidayCalendar>) -> Vec<String> {
+    let days = (to - from).num_days();
+    let sign = if days < 0 { "-" } else { "" };
+    let days = days.unsigned_abs() as u32;
+    let (years, months, day_count) = calendar_diff(from, to);
+    let with_rest = |count: u32, unit: &str, rest: u32| match (count, rest) {
+        (_, 0) => format!("{}{}", sign, plural(count, unit)),
+        (0, rest) => format!("{}{}", sign, plural(rest, "day")),
+        (count, rest) => format!("{}{} {}", sign, plural(count, unit), plural(rest, "day")),
+    };
+    vec![
+        format!("days: {}{}", sign, days),
+        format!("weeks: {}", with_rest(days / 7, "week", days % 7)),
+        format!("months: {}", with_rest(years * 12 + months, "month", day_count)),
+        format!("years: {}{}", sign, format_units(years, months, day_count, 3)),
+        format!("business days: {}", business_days(from, to, holidays)),
+    ]
+}
+
+// true for monday to friday
+pub fn is_weekday(date: NaiveDate) -> bool {
+    !matches!(date.weekday(), Weekday::Sat | Weekday::Sun)
+}
+
+// weekday that is not a holiday of the calendar
+pub fn is_business_day(date: NaiveDate, holidays: Option<&HolidayCalendar>) -> bool {
+    is_weekday(date) && !holidays.is_some_and(|holidays| holidays.is_holiday(date))
+}
+
+// business days after from up to and including to, negative if to is before from
+pub fn business_days(from: NaiveDate, to: NaiveDate, holidays: Option<&HolidayCalendar>) -> i64 {
+    let (start, end, sign) = if from <= to { (from, to, 1) } else { (to, from, -1) };
+    let count = start.iter_days().skip(1).take_while(|date| *date <= end).filter(|date| is_business_day(*date, holidays)).count() as i64;
+    sign * count
+}
+
+// length of the span between the dates, "67 days (2 months 6 days)" or "47 business days"
+pub fn format_span(from: NaiveDate, to: NaiveDate, business: bool, holidays: Option<&HolidayCalendar>) -> String {
+    if business {
+        let count = business_days(from, to, holidays).unsigned_abs() as u32;
+        return plural(count, "business day");
+    }
+    let days = (to - from).num_days().unsigned_abs() as u32;
+    let (years, months, day_count) = calendar_diff(from, to);
+    if years == 0 && months == 0 {
+        plural(days, "day")
+    } else {
+        format!("{} ({})", plural(days, "day"), format_units(years, months, day_count, 3))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).expect("Valid date")
+    }
+
+    #[test]
+    fn test_calendar_diff_and_relative() {
+        assert_eq!(calendar_diff(date(2023, 1, 31), date(2023, 2, 28)), (0, 0, 28));
+        assert_eq!(calendar_diff(date(2023, 1, 31), date(2023, 3, 1)), (0, 1, 1));
+        assert_eq!(calendar_diff(date(2023, 11, 14), date(2020, 9, 1)), (3, 2, 13));
+        let today = date(2023, 11, 14);
+        assert_eq!(format_relative(date(2023, 11, 26), today), "in 12 days");
+        assert_eq!(format_relative(date(2020, 9, 1), today), "3 years 2 months ago");
+        assert_eq!(format_relative(date(2023, 11, 15), today), "tomorrow");
+        assert_eq!(format_relative(date(2024, 11, 14), today), "in 1 year");
+    }
+
+    #[test]
+    fn test_business_days_and_span() {
+        // friday to next friday
+        assert_eq!(business_days(date(2023, 11, 10), date(2023, 11, 17), None), 5);
+        assert_eq!(business_days(date(2023, 11, 17), date(2023, 11, 10), None), -5);
+        assert_eq!(business_days(date(2023, 11, 11), date(2023, 11, 12), None), 0);
+        assert_eq!(format_span(date(2023, 11, 10), date(2023, 11, 17), true, None), "5 business days");
+        assert_eq!(format_span(date(2023, 9, 26), date(2023, 11, 14), false, None), "49 days (1 month 19 days)");
+        // christmas week has three holidays in finland
+        let holidays = HolidayCalendar::new("FI").unwrap();
+        assert_eq!(business_days(date(2023, 12, 22), date(2023, 12, 29), Some(&holidays)), 3);
+        assert_eq!(Offset::BusinessDays(2).apply_with(date(2023, 12, 22), Some(&holidays)), Some(date(2023, 12, 28)));
+        assert_eq!(Offset::BusinessDays(-1).apply(date(2023, 11, 13)), Some(date(2023, 11, 10)));
+    }
+
+    #[test]
+    fn test_parse_date_and_diff() {
+        let today = date(2023, 11, 14);
+        assert_eq!(parse_date("today", today), Ok(today));
+        assert_eq!(parse_date("2020-02-29", today), Ok(date(2020, 2, 29)));
+        assert!(parse_date("2023-02-29", today).is_err() && parse_date("14.11.2023", today).is_err());
+        assert_eq!(format_diff(date(2020, 9, 1), today, None), vec!["days: 1169", "weeks: 167 weeks", "months: 38 months 13 days", "years: 3 years 2 months 13 days", "business days: 835"]);
+        assert_eq!(format_diff(date(2023, 11, 15), today, None)[1], "weeks: -1 day");
+    }
+
+    #[test]
+    fn test_offset_parse_and_apply() {
+        assert_eq!(Offset::parse("14d"), Ok(Offset::Days(14)));
+        assert_eq!(Offset::parse("+3W"), Ok(Offset::Weeks(3)));
+        assert_eq!(Offset::parse("-2m"), Ok(Offset::Months(-2)));
+        assert_eq!(Offset::parse("10"), Ok(Offset::Days(10)));
+        assert!(Offset::parse("3x").is_err() && Offset::parse("w").is_err());
+        assert_eq!(Offset::Months(1).apply(date(2024, 1, 31)), Some(date(2024, 2, 29)));
+        assert_eq!(Offset::Years(-1).apply(date(2024, 2, 29)), Some(date(2023, 2, 28)));
+        assert_eq!(Offset::Weeks(3).apply(date(2023, 11, 14)), Some(date(2023, 12, 5)));
+    }
+
+    #[test]
+    fn test_offset_date() {
+        let holidays = HolidayCalendar::new("FI").unwrap();
+        assert_eq!(offset_date(date(2023, 11, 14), "+3w", None), Ok(date(2023, 12, 5)));
+        assert_eq!(offset_date(date(2023, 12, 22), "+2b", Some(&holidays)), Ok(date(2023, 12, 28)));
+        assert!(offset_date(date(2023, 11, 14), "+3x", None).is_err());
+        assert!(offset_date(NaiveDate::MAX, "+1d", None).is_err());
+    }
+}
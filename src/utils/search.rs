@@ -0,0 +1,245 @@
+use crate::utils::all_utils::{Event, StringFormat};
+use crate::utils::color::Palette;
+use crate::utils::index::stems;
+use crate::utils::normalize::fold_char;
+use crate::utils::registry::edit_distance;
+use crate::utils::timezone::ViewZone;
+
+// scores of a term matching a word
+const EXACT_SCORE: f64 = 1.0;
+const PREFIX_SCORE: f64 = 0.9;
+const CONTAINS_SCORE: f64 = 0.7;
+const STEM_SCORE: f64 = 0.65;
+const FUZZY_SCORE: f64 = 0.6;
+// matches in category count less than in description
+const CATEGORY_WEIGHT: f64 = 0.8;
+// terms shorter than this must match exactly, without typos
+const MIN_FUZZY_LENGTH: usize = 3;
+// color code of highlighted characters, bold and underlined
+const HIGHLIGHT: &str = "1;4";
+
+// event found by search with its score and matched characters of description and category
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchHit {
+    pub event: Event,
+    pub score: f64,
+    description_marks: Vec<bool>,
+    category_marks: Vec<bool>,
+}
+
+
+// char ranges of the words in text
+fn words(text: &[char]) -> Vec<(usize, usize)> {
+    let mut words = Vec::new();
+    let mut start = None;
+    for (index, c) in text.iter().enumerate() {
+        match (c.is_alphanumeric(), start) {
+            (true, None) => start = Some(index),
+            (false, Some(word_start)) => {
+                words.push((word_start, index));
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(word_start) = start {
+        words.push((word_start, text.len()));
+    }
+    words
+}
+
+// allowed typos in a term of given length
+fn allowed_typos(length: usize) -> usize {
+    if length < MIN_FUZZY_LENGTH {
+        0
+    } else if length <= 5 {
+        1
+    } else {
+        2
+    }
+}
+
+// score of the term matching the word and matched char offsets in the word. Inflected forms with the
+// same finnish or english stem match, like "talossa" and "talo". Typos are allowed against the start
+// of the word, so "catalna" finds "catalina" and "sonom" finds "sonoma"
+pub(crate) fn match_word(term: &[char], word: &[char]) -> Option<(f64, Vec<usize>)> {
+    if word == term {
+        return Some((EXACT_SCORE, (0..word.len()).collect()));
+    }
+    if word.starts_with(term) {
+        return Some((PREFIX_SCORE, (0..term.len()).collect()));
+    }
+    if let Some(position) = word.windows(term.len()).position(|window| window == term) {
+        return Some((CONTAINS_SCORE, (position..position + term.len()).collect()));
+    }
+    let term_text: String = term.iter().collect();
+    let term_stems = stems(&term_text);
+    if stems(&word.iter().collect::<String>()).iter().any(|stem| term_stems.contains(stem)) {
+        return Some((STEM_SCORE, (0..word.len()).collect()));
+    }
+    let (distance, length) = match_typos(term, word)?;
+    // mark characters of the word that follow the term in order, a typo in the term may be skipped
+    let mut marks = Vec::new();
+    let mut next = 0;
+    for (offset, c) in word[..length].iter().enumerate() {
+        if term.get(next) == Some(c) {
+            marks.push(offset);
+            next += 1;
+        } else if term.get(next + 1) == Some(c) {
+            marks.push(offset);
+            next += 2;
+        }
+    }
+    Some((FUZZY_SCORE * (1.0 - distance as f64 / term.len() as f64), marks))
+}
+
+// edit distance of the term to the start of the word and length of that start, if within allowed typos
+pub(crate) fn match_typos(term: &[char], word: &[char]) -> Option<(usize, usize)> {
+    let allowed = allowed_typos(term.len());
+    if allowed == 0 {
+        return None;
+    }
+    let term_text: String = term.iter().collect();
+    let (distance, length) = (term.len().saturating_sub(allowed)..=term.len() + allowed)
+        .filter(|length| *length > 0 && *length <= word.len())
+        .map(|length| (edit_distance(&term_text, &word[..length].iter().collect::<String>()), length))
+        .min()?;
+    (distance <= allowed).then_some((distance, length))
+}
+
+// best match of the term in the text, marks the matched characters
+fn match_text(term: &[char], text: &[char], marks: &mut [bool]) -> f64 {
+    let lower_text: Vec<char> = text.iter().map(|c| fold_char(*c)).collect();
+    let mut best: Option<(f64, usize, Vec<usize>)> = None;
+    for (start, end) in words(&lower_text) {
+        if let Some((score, offsets)) = match_word(term, &lower_text[start..end]) {
+            if best.as_ref().is_none_or(|(best_score, _, _)| score > *best_score) {
+                best = Some((score, start, offsets));
+            }
+        }
+    }
+    match best {
+        Some((score, start, offsets)) => {
+            for offset in offsets {
+                marks[start + offset] = true;
+            }
+            score
+        }
+        None => 0.0,
+    }
+}
+
+// rank events by fuzzy similarity of the query words to description and category. Every word has
+// to match. Best matches first, equal scores in date order
+pub fn search_events(events: &[Event], query: &str) -> Vec<SearchHit> {
+    // terms are split to words like the searched text, so "1.70" is "1" and "70"
+    let query: Vec<char> = query.chars().map(fold_char).collect();
+    let terms: Vec<&[char]> = words(&query).into_iter().map(|(start, end)| &query[start..end]).collect();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+    let mut hits = Vec::new();
+    for event in events {
+        let description: Vec<char> = event.description().chars().collect();
+        let category: Vec<char> = event.format_category(StringFormat::Csv).chars().collect();
+        let mut description_marks = vec![false; description.len()];
+        let mut category_marks = vec![false; category.len()];
+        let mut score = 0.0;
+        let mut all_found = true;
+        for term in &terms {
+            let term_score = match_text(term, &description, &mut description_marks).max(CATEGORY_WEIGHT * match_text(term, &category, &mut category_marks));
+            if term_score == 0.0 {
+                all_found = false;
+                break;
+            }
+            score += term_score;
+        }
+        if all_found {
+            hits.push(SearchHit { event: event.clone(), score: score / terms.len() as f64, description_marks, category_marks });
+        }
+    }
+    hits.sort_by(|a, b| b.score.total_cmp(&a.score).then_with(|| a.event.cmp(&b.event)));
+    hits
+}
+
+// paint marked characters of the text
+fn highlight(text: &str, marks: &[bool], palette: &Palette) -> String {
+    let mut output = String::new();
+    let mut run = String::new();
+    let mut run_marked = false;
+    for (c, marked) in text.chars().zip(marks.iter().copied().chain(std::iter::repeat(false))) {
+        if marked != run_marked && !run.is_empty() {
+            output.push_str(&if run_marked { palette.paint(&run, Some(HIGHLIGHT)) } else { run.clone() });
+            run.clear();
+        }
+        run_marked = marked;
+        run.push(c);
+    }
+    output.push_str(&if run_marked { palette.paint(&run, Some(HIGHLIGHT)) } else { run });
+    output
+}
+
+// format hit like printed events, matched characters highlighted
+pub fn format_hit(hit: &SearchHit, zone: &ViewZone, palette: &Palette) -> String {
+    let category = if hit.event.category().is_empty() {
+        "/".to_string()
+    } else {
+        highlight(&hit.event.format_category(StringFormat::Csv), &hit.category_marks, palette)
+    };
+    format!(
+        "{}: {}, {}",
+        hit.event.format_date(&StringFormat::Print, zone),
+        highlight(hit.event.description(), &hit.description_marks, palette),
+        category
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn create_test_events() -> Vec<Event> {
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).expect("Valid date");
+        vec![
+            Event::new(date(2019, 10, 7), "macOS 10.15 Catalina released".to_string(), vec!["apple".to_string()]),
+            Event::new(date(2023, 9, 26), "macOS 14 Sonoma released".to_string(), vec!["apple".to_string()]),
+            Event::new(date(2023, 11, 14), ".NET 8 released".to_string(), vec!["microsoft".to_string()]),
+        ]
+    }
+
+    #[test]
+    fn test_search_tolerates_typos() {
+        assert_eq!(search_events(&create_test_events(), "catalna").len(), 1);
+        let hits = search_events(&create_test_events(), "catlina");
+
+        assert_eq!(hits.len(), 1);
+        assert_eq!(hits[0].event.description(), "macOS 10.15 Catalina released");
+        assert_eq!(format_hit(&hits[0], &ViewZone::Local, &Palette::plain()), "2019-10-07: macOS 10.15 Catalina released, apple");
+        // characters of "catalina" that are in the term are marked
+        let marked: String = "macOS 10.15 Catalina released".chars().zip(&hits[0].description_marks).filter(|(_, marked)| **marked).map(|(c, _)| c).collect();
+        assert_eq!(marked, "Catlina");
+    }
+
+    #[test]
+    fn test_search_ranks_best_first_and_needs_all_terms() {
+        let hits = search_events(&create_test_events(), "released macos");
+        let descriptions: Vec<&str> = hits.iter().map(|hit| hit.event.description()).collect();
+        assert_eq!(descriptions, vec!["macOS 10.15 Catalina released", "macOS 14 Sonoma released"]);
+        // version numbers are split to words like in the description
+        assert_eq!(search_events(&create_test_events(), "10.1").len(), 1);
+
+        // exact word beats typo
+        let hits = search_events(&create_test_events(), "sonoma");
+        assert!(hits[0].score > search_events(&create_test_events(), "sonona")[0].score);
+        // category matches too
+        assert_eq!(search_events(&create_test_events(), "microsft").len(), 1);
+        assert!(search_events(&create_test_events(), "xyz").is_empty());
+    }
+
+    #[test]
+    fn test_highlight_with_colors() {
+        let palette = Palette::new(true, &crate::utils::registry::CategoryRegistry::default());
+        assert_eq!(highlight("abc", &[false, true, true], &palette), "a\x1b[1;4mbc\x1b[0m");
+    }
+}
@@ -0,0 +1,106 @@
+use std::cmp::Ordering;
+use crate::utils::all_utils::Event;
+use crate::utils::normalize::{fold, fold_path};
+
+// sort orders of --sort
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SortKey {
+    Date,
+    Description,
+    Category,
+}
+
+impl SortKey {
+    pub fn parse(input: &str) -> Result<Self, String> {
+        match input.trim().to_lowercase().as_str() {
+            "date" => Ok(SortKey::Date),
+            "description" => Ok(SortKey::Description),
+            "category" => Ok(SortKey::Category),
+            other => Err(format!("Unknown sort: {}. Use date, description or category", other)),
+        }
+    }
+
+    // descriptions and categories are compared without case, ties are in date order
+    fn compare(&self, a: &Event, b: &Event) -> Ordering {
+        match self {
+            SortKey::Date => a.cmp(b),
+            SortKey::Description => fold(a.description()).cmp(&fold(b.description())).then_with(|| a.cmp(b)),
+            SortKey::Category => fold_path(a.category()).cmp(&fold_path(b.category())).then_with(|| a.cmp(b)),
+        }
+    }
+}
+
+// sort events by the key, reversed order if asked
+pub fn sort_events(events: &mut [Event], key: SortKey, reverse: bool) {
+    events.sort_by(|a, b| {
+        let ordering = key.compare(a, b);
+        if reverse {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+}
+
+// keep part of sorted events: skip offset events and take at most limit, or take only the last tail events
+pub fn slice_events(events: Vec<Event>, offset: usize, limit: Option<usize>, tail: Option<usize>) -> Vec<Event> {
+    if let Some(tail) = tail {
+        let skip = events.len().saturating_sub(tail);
+        return events.into_iter().skip(skip).collect();
+    }
+    events.into_iter().skip(offset).take(limit.unwrap_or(usize::MAX)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn create_test_events() -> Vec<Event> {
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).expect("Valid date");
+        vec![
+            Event::new(date(2023, 11, 14), ".NET 8 released".to_string(), vec!["microsoft".to_string()]),
+            Event::new(date(2022, 11, 8), "java 19 released".to_string(), vec!["java".to_string()]),
+            Event::new(date(2023, 3, 9), "Rust 1.68 released".to_string(), vec!["rust".to_string()]),
+        ]
+    }
+
+    fn descriptions(events: &[Event]) -> Vec<&str> {
+        events.iter().map(Event::description).collect()
+    }
+
+    #[test]
+    fn test_sort_events_by_key_and_reverse() {
+        let mut events = create_test_events();
+        sort_events(&mut events, SortKey::Description, false);
+        assert_eq!(descriptions(&events), vec![".NET 8 released", "java 19 released", "Rust 1.68 released"]);
+
+        sort_events(&mut events, SortKey::Date, true);
+        assert_eq!(descriptions(&events), vec![".NET 8 released", "Rust 1.68 released", "java 19 released"]);
+
+        sort_events(&mut events, SortKey::Category, false);
+        assert_eq!(descriptions(&events), vec!["java 19 released", ".NET 8 released", "Rust 1.68 released"]);
+    }
+
+    #[test]
+    fn test_sort_by_category_ignores_case() {
+        let date = NaiveDate::from_ymd_opt(2023, 11, 14).expect("Valid date");
+        let mut events = vec![
+            Event::new(date, "b".to_string(), vec!["beta".to_string()]),
+            Event::new(date, "a".to_string(), vec!["Alpha".to_string()]),
+            Event::new(date, "c".to_string(), vec!["Charlie".to_string()]),
+        ];
+        sort_events(&mut events, SortKey::Category, false);
+        assert_eq!(descriptions(&events), vec!["a", "b", "c"]);
+    }
+
+    #[test]
+    fn test_slice_events_offset_limit_and_tail() {
+        let mut events = create_test_events();
+        sort_events(&mut events, SortKey::Date, false);
+
+        assert_eq!(descriptions(&slice_events(events.clone(), 1, Some(1), None)), vec!["Rust 1.68 released"]);
+        assert_eq!(descriptions(&slice_events(events.clone(), 0, None, Some(2))), vec!["Rust 1.68 released", ".NET 8 released"]);
+        assert!(slice_events(events, 5, None, None).is_empty());
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::BTreeMap;
+use chrono::Datelike;
+use crate::utils::all_utils::{Event, StringFormat};
+use crate::utils::html::escape_json;
+use crate::utils::timezone::ViewZone;
+
+const MONTHS: [&str; 12] = ["January", "February", "March", "April", "May", "June", "July", "August", "September", "October", "November", "December"];
+const WEEKDAYS: [&str; 7] = ["Monday", "Tuesday", "Wednesday", "Thursday", "Friday", "Saturday", "Sunday"];
+
+// numbers about a set of events, dates as seen in the zone
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stats {
+    pub total: usize,
+    // uncategorized events are counted as "/"
+    pub per_primary: BTreeMap<String, usize>,
+    // "primary/secondary", events without secondary category are not counted
+    pub per_secondary: BTreeMap<String, usize>,
+    pub per_year: BTreeMap<i32, usize>,
+    // month of year from january
+    pub per_month: [usize; 12],
+    // weekday from monday
+    pub per_weekday: [usize; 7],
+    pub first: Option<Event>,
+    pub last: Option<Event>,
+    // days between consecutive events and the events around the gap
+    pub longest_gap: Option<(i64, Event, Event)>,
+    // events per calendar year from the first year to the last year
+    pub average_per_year: f64,
+}
+
+impl Stats {
+    pub fn new(events: &[Event], zone: &ViewZone) -> Self {
+        let mut events: Vec<&Event> = events.iter().collect();
+        events.sort();
+        let mut stats = Stats {
+            total: events.len(),
+            per_primary: BTreeMap::new(),
+            per_secondary: BTreeMap::new(),
+            per_year: BTreeMap::new(),
+            per_month: [0; 12],
+            per_weekday: [0; 7],
+            first: events.first().map(|event| (*event).clone()),
+            last: events.last().map(|event| (*event).clone()),
+            longest_gap: None,
+            average_per_year: 0.0,
+        };
+
+        for event in &events {
+            let date = event.date_in(zone);
+            let primary = if event.primary_category().is_empty() { "/" } else { event.primary_category() };
+            *stats.per_primary.entry(primary.to_string()).or_insert(0) += 1;
+            if !event.secondary_category().is_empty() {
+                *stats.per_secondary.entry(format!("{}/{}", primary, event.secondary_category())).or_insert(0) += 1;
+            }
+            *stats.per_year.entry(date.year()).or_insert(0) += 1;
+            stats.per_month[date.month0() as usize] += 1;
+            stats.per_weekday[date.weekday().num_days_from_monday() as usize] += 1;
+        }
+
+        // first of equally long gaps is kept
+        for pair in events.windows(2) {
+            let gap = (pair[1].date_in(zone) - pair[0].date_in(zone)).num_days();
+            if stats.longest_gap.as_ref().is_none_or(|(longest, _, _)| gap > *longest) {
+                stats.longest_gap = Some((gap, pair[0].clone(), pair[1].clone()));
+            }
+        }
+
+        if let (Some(first), Some(last)) = (&stats.first, &stats.last) {
+            let years = last.date_in(zone).year() - first.date_in(zone).year() + 1;
+            stats.average_per_year = stats.total as f64 / f64::from(years);
+        }
+        stats
+    }
+}
+
+// aligned "name  count" lines under a section title
+fn section(lines: &mut Vec<String>, title: &str, counts: &[(String, usize)]) {
+    if counts.is_empty() {
+        return;
+    }
+    let width = counts.iter().map(|(name, _)| name.chars().count()).max().unwrap_or(0);
+    lines.push(String::new());
+    lines.push(format!("{}:", title));
+    for (name, count) in counts {
+        lines.push(format!("  {:<width$}  {:>5}", name, count, width = width));
+    }
+}
+
+// render stats as lines of sections with aligned counts
+pub fn render_stats(stats: &Stats, zone: &ViewZone) -> Vec<String> {
+    let mut lines = vec![format!("Total events: {}", stats.total)];
+    if let (Some(first), Some(last)) = (&stats.first, &stats.last) {
+        lines.push(format!("First event:  {}", first.format_to_string_in(StringFormat::Print, zone)));
+        lines.push(format!("Last event:   {}", last.format_to_string_in(StringFormat::Print, zone)));
+    }
+    if let Some((days, from, to)) = &stats.longest_gap {
+        lines.push(format!("Longest gap:  {} days, {} - {}", days, from.date_in(zone), to.date_in(zone)));
+    }
+    lines.push(format!("Average per year: {:.2}", stats.average_per_year));
+
+    let named = |counts: &BTreeMap<String, usize>| counts.iter().map(|(name, count)| (name.clone(), *count)).collect::<Vec<_>>();
+    section(&mut lines, "Primary categories", &named(&stats.per_primary));
+    section(&mut lines, "Secondary categories", &named(&stats.per_secondary));
+    section(&mut lines, "Years", &stats.per_year.iter().map(|(year, count)| (year.to_string(), *count)).collect::<Vec<_>>());
+    if stats.total > 0 {
+        section(&mut lines, "Months", &MONTHS.iter().zip(stats.per_month).map(|(name, count)| (name.to_string(), count)).collect::<Vec<_>>());
+        section(&mut lines, "Weekdays", &WEEKDAYS.iter().zip(stats.per_weekday).map(|(name, count)| (name.to_string(), count)).collect::<Vec<_>>());
+    }
+    lines
+}
+
+fn json_object<'a>(counts: impl Iterator<Item = (String, &'a usize)>) -> String {
+    let fields: Vec<String> = counts.map(|(name, count)| format!("\"{}\":{}", escape_json(&name), count)).collect();
+    format!("{{{}}}", fields.join(","))
+}
+
+fn json_event(event: &Option<Event>, zone: &ViewZone) -> String {
+    match event {
+        Some(event) => format!(
+            "{{\"id\":\"{}\",\"date\":\"{}\",\"description\":\"{}\",\"category\":\"{}\"}}",
+            event.id(),
+            event.date_in(zone),
+            escape_json(event.description()),
+            escape_json(&event.format_category(StringFormat::Csv))
+        ),
+        None => "null".to_string(),
+    }
+}
+
+// render stats as a json object
+pub fn stats_json(stats: &Stats, zone: &ViewZone) -> String {
+    let longest_gap = match &stats.longest_gap {
+        Some((days, from, to)) => format!("{{\"days\":{},\"from\":\"{}\",\"to\":\"{}\"}}", days, from.date_in(zone), to.date_in(zone)),
+        None => "null".to_string(),
+    };
+    let fields = [
+        format!("\"total\":{}", stats.total),
+        format!("\"first\":{}", json_event(&stats.first, zone)),
+        format!("\"last\":{}", json_event(&stats.last, zone)),
+        format!("\"longest_gap\":{}", longest_gap),
+        format!("\"average_per_year\":{:.2}", stats.average_per_year),
+        format!("\"primary\":{}", json_object(stats.per_primary.iter().map(|(name, count)| (name.clone(), count)))),
+        format!("\"secondary\":{}", json_object(stats.per_secondary.iter().map(|(name, count)| (name.clone(), count)))),
+        format!("\"years\":{}", json_object(stats.per_year.iter().map(|(year, count)| (year.to_string(), count)))),
+        format!("\"months\":{}", json_object(MONTHS.iter().map(|name| name.to_string()).zip(stats.per_month.iter()))),
+        format!("\"weekdays\":{}", json_object(WEEKDAYS.iter().map(|name| name.to_string()).zip(stats.per_weekday.iter()))),
+    ];
+    format!("{{{}}}", fields.join(","))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn create_test_events() -> Vec<Event> {
+        let date = |year, month, day| NaiveDate::from_ymd_opt(year, month, day).expect("Valid date");
+        vec![
+            Event::new(date(2022, 11, 8), ".NET 7 released".to_string(), vec!["microsoft".to_string(), "dotnet".to_string()]),
+            Event::new(date(2023, 11, 14), ".NET 8 released".to_string(), vec!["microsoft".to_string(), "dotnet".to_string()]),
+            Event::new(date(2023, 3, 9), "Rust 1.68 released".to_string(), vec!["rust".to_string()]),
+            Event::new(date(2024, 1, 1), "New year".to_string(), Vec::new()),
+        ]
+    }
+
+    #[test]
+    fn test_stats_counts_and_gap() {
+        let stats = Stats::new(&create_test_events(), &ViewZone::Local);
+
+        assert_eq!(stats.total, 4);
+        assert_eq!(stats.per_primary.get("microsoft"), Some(&2));
+        assert_eq!(stats.per_primary.get("/"), Some(&1));
+        assert_eq!(stats.per_secondary.get("microsoft/dotnet"), Some(&2));
+        assert_eq!(stats.per_year.get(&2023), Some(&2));
+        assert_eq!(stats.per_month[10], 2);
+        // 2022-11-08 and 2023-11-14 are tuesdays
+        assert_eq!(stats.per_weekday[1], 2);
+        assert_eq!(stats.first.as_ref().map(Event::description), Some(".NET 7 released"));
+        let (days, from, _) = stats.longest_gap.clone().unwrap();
+        assert_eq!((days, from.description()), (250, "Rust 1.68 released"));
+        assert!((stats.average_per_year - 4.0 / 3.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_stats_json_and_empty() {
+        let json = stats_json(&Stats::new(&create_test_events(), &ViewZone::Local), &ViewZone::Local);
+        assert!(json.starts_with("{\"total\":4,\"first\":{\"id\":"));
+        assert!(json.contains("\"longest_gap\":{\"days\":250,\"from\":\"2023-03-09\",\"to\":\"2023-11-14\"}"));
+        assert!(json.contains("\"years\":{\"2022\":1,\"2023\":2,\"2024\":1}"));
+
+        let empty = Stats::new(&[], &ViewZone::Local);
+        assert_eq!(render_stats(&empty, &ViewZone::Local), vec!["Total events: 0".to_string(), "Average per year: 0.00".to_string()]);
+        assert!(stats_json(&empty, &ViewZone::Local).contains("\"first\":null"));
+    }
+}
@@ -0,0 +1,195 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use chrono::{Datelike, NaiveDate};
+use crate::utils::all_utils::{stable_hash, Event};
+use crate::utils::timezone::ViewZone;
+
+// layout of the drawing in pixels
+const WIDTH: f64 = 1200.0;
+const LANE_LABEL_WIDTH: f64 = 140.0;
+const RIGHT_MARGIN: f64 = 40.0;
+const TOP_MARGIN: f64 = 30.0;
+const LANE_HEADER: f64 = 22.0;
+const LABEL_ROW: f64 = 14.0;
+const LANE_GAP: f64 = 10.0;
+const FONT_SIZE: f64 = 11.0;
+// estimated width of one character of the label font
+const CHAR_WIDTH: f64 = 6.2;
+// lane colors picked by hash of the primary category
+const LANE_COLORS: [&str; 8] = ["#1f77b4", "#ff7f0e", "#2ca02c", "#d62728", "#9467bd", "#8c564b", "#e377c2", "#17becf"];
+
+// escape text for xml content and attributes
+pub fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+// one label placed in a lane
+struct Label<'a> {
+    event: &'a Event,
+    x: f64,
+    row: usize,
+    // label is drawn to the left of the marker when there is no room on the right
+    anchor_end: bool,
+    // description, cut when it fits on neither side of the marker
+    text: String,
+}
+
+// place labels of one lane on rows so that they dont overlap. Labels stay between the lane names and
+// the right margin, labels fitting on neither side of the marker are cut to the wider side
+fn place_labels<'a>(events: &[&'a Event], x_of: impl Fn(NaiveDate) -> f64, zone: &ViewZone) -> Vec<Label<'a>> {
+    let right_edge = WIDTH - RIGHT_MARGIN;
+    // rightmost used x of every row
+    let mut row_ends: Vec<f64> = Vec::new();
+    let mut labels = Vec::new();
+    for event in events {
+        let x = x_of(event.date_in(zone));
+        let mut text = event.description().to_string();
+        let (right, left) = (right_edge - x, x - LANE_LABEL_WIDTH);
+        let width_of = |text: &str| text.chars().count() as f64 * CHAR_WIDTH;
+        let anchor_end = width_of(&text) > right && left > right;
+        let room = if anchor_end { left } else { right };
+        if width_of(&text) > room {
+            let chars = (room / CHAR_WIDTH).max(1.0) as usize;
+            text = text.chars().take(chars - 1).chain(['…']).collect();
+        }
+        let text_width = width_of(&text);
+        let (start, end) = if anchor_end { (x - text_width, x) } else { (x, x + text_width) };
+        let row = match row_ends.iter().position(|row_end| *row_end + CHAR_WIDTH < start) {
+            Some(row) => row,
+            None => {
+                row_ends.push(f64::MIN);
+                row_ends.len() - 1
+            }
+        };
+        row_ends[row] = end;
+        labels.push(Label { event, x, row, anchor_end, text });
+    }
+    labels
+}
+
+// draw timeline with one lane per primary category, events as labeled markers and year gridlines
+pub fn render_svg(events: &[Event], zone: &ViewZone) -> String {
+    let mut events: Vec<&Event> = events.iter().collect();
+    events.sort();
+
+    // full years from the first to the last event
+    let first_year = events.first().map(|event| event.date_in(zone).year()).unwrap_or(2000);
+    let last_year = events.last().map(|event| event.date_in(zone).year()).unwrap_or(first_year);
+    let start = NaiveDate::from_ymd_opt(first_year, 1, 1).unwrap_or_default();
+    let end = NaiveDate::from_ymd_opt(last_year + 1, 1, 1).unwrap_or_default();
+    let span = (end - start).num_days().max(1) as f64;
+    let plot_width = WIDTH - LANE_LABEL_WIDTH - RIGHT_MARGIN;
+    let x_of = |date: NaiveDate| LANE_LABEL_WIDTH + (date - start).num_days() as f64 / span * plot_width;
+
+    // events grouped by primary category, uncategorized last
+    let mut lanes: BTreeMap<(bool, String), Vec<&Event>> = BTreeMap::new();
+    for event in &events {
+        let primary = event.primary_category().to_string();
+        lanes.entry((primary.is_empty(), primary)).or_default().push(event);
+    }
+
+    let mut body = String::new();
+    let mut y = TOP_MARGIN;
+    for ((_, primary), lane_events) in &lanes {
+        let labels = place_labels(lane_events, x_of, zone);
+        let rows = labels.iter().map(|label| label.row + 1).max().unwrap_or(1);
+        let height = LANE_HEADER + rows as f64 * LABEL_ROW;
+        let name = if primary.is_empty() { "(none)" } else { primary.as_str() };
+        let color = LANE_COLORS[stable_hash(primary) as usize % LANE_COLORS.len()];
+        let axis_y = y + LANE_HEADER / 2.0;
+
+        let _ = writeln!(body, r#"  <g class="lane">"#);
+        let _ = writeln!(body, r#"    <rect x="0" y="{:.1}" width="{}" height="{:.1}" fill="{}" fill-opacity="0.06"/>"#, y, WIDTH, height, color);
+        let _ = writeln!(body, r#"    <text x="8" y="{:.1}" class="lane-label">{}</text>"#, axis_y + 4.0, escape_xml(name));
+        let _ = writeln!(body, r#"    <line x1="{}" y1="{:.1}" x2="{}" y2="{:.1}" stroke="{}"/>"#, LANE_LABEL_WIDTH, axis_y, WIDTH - RIGHT_MARGIN, axis_y, color);
+        for label in &labels {
+            let label_y = y + LANE_HEADER + label.row as f64 * LABEL_ROW + FONT_SIZE - 2.0;
+            let title = format!("{} {}", label.event.date_in(zone).format("%Y-%m-%d"), label.event.description());
+            let _ = writeln!(body, r#"    <g class="event"><title>{}</title>"#, escape_xml(&title));
+            let _ = writeln!(body, r#"      <line x1="{:.1}" y1="{:.1}" x2="{:.1}" y2="{:.1}" stroke="{}" stroke-opacity="0.4"/>"#, label.x, axis_y, label.x, label_y - FONT_SIZE + 2.0, color);
+            let _ = writeln!(body, r#"      <circle cx="{:.1}" cy="{:.1}" r="4" fill="{}"/>"#, label.x, axis_y, color);
+            let anchor = if label.anchor_end { "end" } else { "start" };
+            let _ = writeln!(body, r#"      <text x="{:.1}" y="{:.1}" text-anchor="{}">{}</text>"#, label.x, label_y, anchor, escape_xml(&label.text));
+            let _ = writeln!(body, "    </g>");
+        }
+        let _ = writeln!(body, "  </g>");
+        y += height + LANE_GAP;
+    }
+    let height = y + 10.0;
+
+    // year gridlines over the lanes
+    let mut grid = String::new();
+    for year in first_year..=last_year + 1 {
+        let Some(date) = NaiveDate::from_ymd_opt(year, 1, 1) else { continue };
+        let x = x_of(date);
+        let _ = writeln!(grid, r##"  <line x1="{:.1}" y1="{}" x2="{:.1}" y2="{:.1}" stroke="#999" stroke-dasharray="2,3"/>"##, x, TOP_MARGIN - 8.0, x, height - 10.0);
+        if year <= last_year {
+            let _ = writeln!(grid, r#"  <text x="{:.1}" y="{}" class="year">{}</text>"#, x + 3.0, TOP_MARGIN - 12.0, year);
+        }
+    }
+
+    let mut svg = String::new();
+    let _ = writeln!(svg, r#"<svg xmlns="http://www.w3.org/2000/svg" width="{}" height="{:.0}" viewBox="0 0 {} {:.0}">"#, WIDTH, height, WIDTH, height);
+    let _ = writeln!(svg, "  <style>text {{ font-family: sans-serif; font-size: {}px; }} .lane-label {{ font-weight: bold; }} .year {{ fill: #666; }}</style>", FONT_SIZE);
+    let _ = writeln!(svg, r#"  <rect width="100%" height="100%" fill="white"/>"#);
+    svg.push_str(&grid);
+    svg.push_str(&body);
+    svg.push_str("</svg>\n");
+    svg
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn event(year: i32, month: u32, day: u32, description: &str, category: &str) -> Event {
+        let category = if category.is_empty() { Vec::new() } else { vec![category.to_string()] };
+        Event::new(NaiveDate::from_ymd_opt(year, month, day).expect("Valid date"), description.to_string(), category)
+    }
+
+    #[test]
+    fn test_render_svg_lanes_and_gridlines() {
+        let events = vec![
+            event(2022, 11, 8, ".NET 7 released", "microsoft"),
+            event(2023, 3, 9, "Rust <1.68> released", "rust"),
+            event(2023, 6, 1, "no category", ""),
+        ];
+        let svg = render_svg(&events, &ViewZone::Local);
+
+        assert!(svg.starts_with("<svg"));
+        assert_eq!(svg.matches(r#"class="lane""#).count(), 3);
+        assert!(svg.contains(">microsoft</text>") && svg.contains(">(none)</text>"));
+        // gridlines from start of 2022 to end of 2023
+        assert_eq!(svg.matches("stroke-dasharray").count(), 3);
+        assert!(svg.contains("Rust &lt;1.68&gt; released"));
+    }
+
+    #[test]
+    fn test_close_labels_go_to_different_rows() {
+        let events = [event(2023, 1, 1, "first long label", "rust"), event(2023, 1, 2, "second long label", "rust")];
+        let lane: Vec<&Event> = events.iter().collect();
+        let labels = place_labels(&lane, |date| date.ordinal() as f64, &ViewZone::Local);
+
+        assert_eq!(labels[0].row, 0);
+        assert_eq!(labels[1].row, 1);
+    }
+
+    #[test]
+    fn test_long_labels_stay_inside_plot() {
+        let long = "x".repeat(300);
+        let events = [event(2023, 1, 1, &long, "rust"), event(2023, 1, 2, "short", "rust")];
+        let lane: Vec<&Event> = events.iter().collect();
+        // first marker near the right edge, second near the lane names
+        let x_of = |date: NaiveDate| if date.day() == 1 { WIDTH - RIGHT_MARGIN - 50.0 } else { LANE_LABEL_WIDTH + 10.0 };
+        let labels = place_labels(&lane, x_of, &ViewZone::Local);
+
+        assert!(labels[0].anchor_end && labels[0].text.ends_with('…'));
+        let width = labels[0].text.chars().count() as f64 * CHAR_WIDTH;
+        assert!(labels[0].x - width >= LANE_LABEL_WIDTH);
+        assert!(!labels[1].anchor_end && labels[1].text == "short");
+    }
+}
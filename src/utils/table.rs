@@ -0,0 +1,197 @@
+use std::io::IsTerminal;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+use crate::utils::all_utils::{Event, StringFormat};
+use crate::utils::color::Palette;
+use crate::utils::relative::format_relative;
+use crate::utils::timezone::ViewZone;
+
+// gap between table columns
+const COLUMN_GAP: &str = "  ";
+// description is not shrunk narrower than this
+const MIN_DESCRIPTION_WIDTH: usize = 10;
+
+// columns available in --columns
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Column {
+    Date,
+    Category,
+    Description,
+    Tags,
+    Id,
+    Relative,
+}
+
+impl Column {
+    // default columns of the table
+    pub const DEFAULT: [Column; 3] = [Column::Date, Column::Category, Column::Description];
+
+    // parse comma separated column names like "date,category,description,id"
+    pub fn parse_list(input: &str) -> Result<Vec<Column>, String> {
+        input
+            .split(',')
+            .map(|name| match name.trim().to_lowercase().as_str() {
+                "date" => Ok(Column::Date),
+                "category" => Ok(Column::Category),
+                "description" => Ok(Column::Description),
+                "tags" => Ok(Column::Tags),
+                "id" => Ok(Column::Id),
+                "relative" => Ok(Column::Relative),
+                other => Err(format!("Unknown column: {}. Use date, category, description, tags, id or relative", other)),
+            })
+            .collect()
+    }
+
+    fn header(&self) -> &'static str {
+        match self {
+            Column::Date => "DATE",
+            Column::Category => "CATEGORY",
+            Column::Description => "DESCRIPTION",
+            Column::Tags => "TAGS",
+            Column::Id => "ID",
+            Column::Relative => "RELATIVE",
+        }
+    }
+
+    fn value(&self, event: &Event, zone: &ViewZone) -> String {
+        match self {
+            Column::Date => event.format_date(&StringFormat::Print, zone),
+            Column::Category => event.format_category(StringFormat::Print),
+            Column::Description => event.description().to_string(),
+            Column::Tags => event.format_tags(&StringFormat::Print),
+            Column::Id => event.id(),
+            Column::Relative => format_relative(event.date_in(zone), zone.today()),
+        }
+    }
+}
+
+// width of the terminal if stdout is one, otherwise COLUMNS variable or no limit
+pub fn terminal_width() -> usize {
+    if std::io::stdout().is_terminal() {
+        if let Some((terminal_size::Width(width), _)) = terminal_size::terminal_size() {
+            return usize::from(width);
+        }
+    }
+    std::env::var("COLUMNS").ok().and_then(|columns| columns.parse().ok()).unwrap_or(usize::MAX)
+}
+
+// cut string to given display width, ending with ellipsis if cut
+pub fn truncate_to_width(text: &str, width: usize) -> String {
+    if text.width() <= width {
+        return text.to_string();
+    }
+    let mut result = String::new();
+    let mut used = 0;
+    for c in text.chars() {
+        let char_width = c.width().unwrap_or(0);
+        // leave room for the ellipsis
+        if used + char_width + 1 > width {
+            break;
+        }
+        result.push(c);
+        used += char_width;
+    }
+    if width > 0 {
+        result.push('…');
+    }
+    result
+}
+
+// render events as aligned table lines with header, fitting the table in given width.
+// dates and categories are colored with the palette
+pub fn render_table(events: &[Event], columns: &[Column], max_width: usize, zone: &ViewZone, palette: &Palette) -> Vec<String> {
+    let rows: Vec<Vec<String>> = events
+        .iter()
+        .map(|event| columns.iter().map(|column| column.value(event, zone)).collect())
+        .collect();
+
+    // natural width of every column
+    let mut widths: Vec<usize> = columns.iter().map(|column| column.header().width()).collect();
+    for row in &rows {
+        for (width, value) in widths.iter_mut().zip(row) {
+            *width = (*width).max(value.width());
+        }
+    }
+
+    // shrink description, then category, when the table is too wide
+    let gaps = COLUMN_GAP.len() * columns.len().saturating_sub(1);
+    for (shrinkable, min_width) in [(Column::Description, MIN_DESCRIPTION_WIDTH), (Column::Category, Column::Category.header().len())] {
+        let total: usize = widths.iter().sum::<usize>() + gaps;
+        if total <= max_width {
+            break;
+        }
+        if let Some(index) = columns.iter().position(|column| *column == shrinkable) {
+            widths[index] = widths[index].saturating_sub(total - max_width).max(min_width);
+        }
+    }
+
+    // pad outside of colors so the escape codes dont count in the width
+    let today = zone.today();
+    let format_row = |values: Vec<String>, event: Option<&Event>| {
+        let cells: Vec<String> = values
+            .iter()
+            .zip(&widths)
+            .zip(columns)
+            .map(|((value, width), column)| {
+                let text = truncate_to_width(value, *width);
+                let padding = " ".repeat(width.saturating_sub(text.width()));
+                let text = match (event, column) {
+                    (Some(event), Column::Date) => palette.paint_date(&text, event.date_in(zone), today),
+                    (Some(event), Column::Category) => palette.paint_category(&text, event),
+                    _ => text,
+                };
+                format!("{}{}", text, padding)
+            })
+            .collect();
+        cells.join(COLUMN_GAP).trim_end().to_string()
+    };
+
+    let mut lines = vec![format_row(columns.iter().map(|column| column.header().to_string()).collect(), None)];
+    lines.extend(rows.into_iter().zip(events).map(|(row, event)| format_row(row, Some(event))));
+    lines
+}
+
+// print events sorted from oldest to latest as a table fitting the terminal
+pub fn print_table(events: &mut [Event], columns: &[Column], zone: &ViewZone, palette: &Palette) {
+    events.sort();
+    for line in render_table(events, columns, terminal_width(), zone, palette) {
+        println!("{}", line);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn create_table_events() -> Vec<Event> {
+        let date = NaiveDate::from_ymd_opt(2024, 4, 15).expect("Valid date");
+        vec![
+            Event::new(date, "Näyttö vaihdettu uuteen malliin".to_string(), vec!["työ".to_string()]),
+            Event::new(date, "short".to_string(), vec!["microsoft".to_string(), "dotnet".to_string()]),
+        ]
+    }
+
+    #[test]
+    fn test_truncate_to_width_unicode() {
+        assert_eq!(truncate_to_width("Näyttö", 10), "Näyttö");
+        assert_eq!(truncate_to_width("Näyttö", 4), "Näy…");
+        // wide characters take two columns
+        assert_eq!(truncate_to_width("日本語", 5), "日本…");
+    }
+
+    #[test]
+    fn test_render_table_aligns_and_fits_width() {
+        let events = create_table_events();
+        let lines = render_table(&events, &Column::DEFAULT, 50, &ViewZone::Local, &Palette::plain());
+
+        assert_eq!(lines[0], "DATE        CATEGORY          DESCRIPTION");
+        assert_eq!(lines[1], "2024-04-15  työ               Näyttö vaihdettu uu…");
+        assert!(lines.iter().all(|line| line.width() <= 50));
+    }
+
+    #[test]
+    fn test_column_parse_list() {
+        assert_eq!(Column::parse_list("date, id").unwrap(), vec![Column::Date, Column::Id]);
+        assert!(Column::parse_list("date,colour").is_err());
+    }
+}
@@ -0,0 +1,142 @@
+use std::collections::BTreeMap;
+use crate::utils::all_utils::Event;
+use crate::utils::normalize::fold;
+
+// Used in filter_by_tags()
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TagMatch {
+    // event has at least one of the tags
+    Any,
+    // event has every tag
+    All,
+    // event has none of the tags, or no tags at all if no tags are given
+    None,
+}
+
+// split tag input like "#release #lts,breaking" to lowercase tags without '#'
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .map(|tag| tag.trim().trim_start_matches('#').to_lowercase())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
+// true if the event tags match the tags in the mode, tags are compared folded so unicode forms match
+fn tags_match(event: &Event, tags: &[String], mode: TagMatch) -> bool {
+    let event_tags: Vec<String> = event.tags().iter().map(|tag| fold(tag)).collect();
+    let has_tag = |tag: &String| event_tags.contains(&fold(tag));
+    match mode {
+        TagMatch::Any => tags.iter().any(has_tag),
+        TagMatch::All => !tags.is_empty() && tags.iter().all(has_tag),
+        TagMatch::None if tags.is_empty() => event.tags().is_empty(),
+        TagMatch::None => !tags.iter().any(has_tag),
+    }
+}
+
+// add events matching the tags to results vector
+pub fn filter_by_tags(orig: &[Event], results: &mut Vec<Event>, tags: &[String], mode: TagMatch) {
+    for event in orig {
+        // add event in result list if its not added already
+        if tags_match(event, tags, mode) && !results.contains(event) {
+            results.push(event.clone());
+        }
+    }
+}
+
+// keep only results matching the tags, used when other filters have chosen the events
+pub fn retain_by_tags(results: &mut Vec<Event>, tags: &[String], mode: TagMatch) {
+    results.retain(|event| tags_match(event, tags, mode));
+}
+
+// count events per tag
+pub fn count_tags(events: &[Event]) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for event in events {
+        for tag in event.tags() {
+            *counts.entry(tag.clone()).or_insert(0) += 1;
+        }
+    }
+    counts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::all_utils::filter_by_string;
+    use chrono::NaiveDate;
+
+    fn create_tagged_events() -> Vec<Event> {
+        let date = NaiveDate::from_ymd_opt(2024, 4, 15).expect("Valid date");
+        vec![
+            Event::new(date, "lts release".to_string(), Vec::new()).with_tags(parse_tags("#release #lts")),
+            Event::new(date, "breaking release".to_string(), Vec::new()).with_tags(parse_tags("#release #breaking")),
+            Event::new(date, "untagged".to_string(), Vec::new()),
+        ]
+    }
+
+    #[test]
+    fn test_parse_tags() {
+        assert_eq!(parse_tags("#Release #lts,breaking #lts"), vec!["breaking", "lts", "release"]);
+        assert!(parse_tags("  ").is_empty());
+        // tags are stored as written, decomposed "a\u{308}" is not composed to "ä"
+        assert_eq!(parse_tags("#Ha\u{308}t"), vec!["ha\u{308}t"]);
+    }
+
+    #[test]
+    fn test_filter_by_tags_ignores_unicode_forms() {
+        let date = NaiveDate::from_ymd_opt(2024, 4, 15).expect("Valid date");
+        let events = vec![Event::new(date, "decomposed".to_string(), Vec::new()).with_tags(parse_tags("#ha\u{308}t"))];
+
+        let mut results = Vec::new();
+        filter_by_tags(&events, &mut results, &parse_tags("#HÄT"), TagMatch::Any);
+        assert_eq!(results.len(), 1);
+    }
+
+    #[test]
+    fn test_filter_by_tags_modes() {
+        let events = create_tagged_events();
+
+        let mut results = Vec::new();
+        filter_by_tags(&events, &mut results, &parse_tags("lts,breaking"), TagMatch::Any);
+        assert_eq!(results.len(), 2);
+
+        let mut results = Vec::new();
+        filter_by_tags(&events, &mut results, &parse_tags("release,lts"), TagMatch::All);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].description(), "lts release");
+
+        let mut results = Vec::new();
+        filter_by_tags(&events, &mut results, &[], TagMatch::None);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].description(), "untagged");
+    }
+
+    #[test]
+    fn test_retain_by_tags_removes_from_results() {
+        let events = create_tagged_events();
+        let mut results = events.clone();
+        retain_by_tags(&mut results, &parse_tags("breaking"), TagMatch::None);
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(count_tags(&events).get("release"), Some(&2));
+    }
+
+    #[test]
+    fn test_no_tag_narrows_category_filter() {
+        let date = NaiveDate::from_ymd_opt(2024, 4, 15).expect("Valid date");
+        let mut events = create_tagged_events();
+        events.push(Event::new(date, "microsoft lts".to_string(), vec!["microsoft".to_string()]).with_tags(parse_tags("#lts")));
+        events.push(Event::new(date, "microsoft plain".to_string(), vec!["microsoft".to_string()]));
+
+        // like list --category microsoft --no-tag lts, untagged events of other categories are not added
+        let mut results = Vec::new();
+        filter_by_string(&events, &mut results, "microsoft", false, true);
+        retain_by_tags(&mut results, &parse_tags("lts"), TagMatch::None);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].description(), "microsoft plain");
+    }
+}
@@ -0,0 +1,282 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::fs::File;
+use std::path::Path;
+use chrono::format::{Item, StrftimeItems};
+use chrono::NaiveDate;
+use csv::{Error, ReaderBuilder};
+use unicode_width::UnicodeWidthStr;
+use crate::utils::all_utils::{Event, StringFormat};
+use crate::utils::table::truncate_to_width;
+use crate::utils::timezone::ViewZone;
+
+// fields usable in templates
+const FIELDS: [&str; 11] = ["date", "time", "datetime", "timezone", "description", "category", "primary", "secondary", "tags", "id", "weekday"];
+
+// part of a parsed template
+#[derive(Debug, Clone, PartialEq)]
+enum Segment {
+    Literal(String),
+    // {field} or {field:spec}, spec is strftime for date and time, padding for others
+    Field { name: String, spec: String },
+    // {?field}...{/field} shown if field is not empty, {!field}...{/field} if it is empty
+    Section { name: String, negate: bool, body: Vec<Segment> },
+}
+
+// user defined line format, for example "{date:%d.%m.%Y} [{category}] {description}"
+#[derive(Debug, Clone, PartialEq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+impl Template {
+    // parse template, "{{" and "}}" are literal braces
+    pub fn parse(input: &str) -> Result<Self, String> {
+        let mut chars = input.chars().peekable();
+        let segments = parse_segments(&mut chars, None)?;
+        Ok(Template { segments })
+    }
+
+    // render event with the template, times are shown in the given zone
+    pub fn render(&self, event: &Event, zone: &ViewZone) -> String {
+        let mut output = String::new();
+        render_segments(&self.segments, event, zone, &mut output);
+        output
+    }
+}
+
+fn parse_segments(chars: &mut std::iter::Peekable<std::str::Chars>, section: Option<&str>) -> Result<Vec<Segment>, String> {
+    let mut segments = Vec::new();
+    let mut literal = String::new();
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                literal.push('{');
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                literal.push('}');
+            }
+            '}' => return Err("Unexpected '}' in template. Use '}}' for a literal brace".to_string()),
+            '{' => {
+                let mut tag = String::new();
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => tag.push(c),
+                        None => return Err(format!("Unclosed '{{{}' in template", tag)),
+                    }
+                }
+                if !literal.is_empty() {
+                    segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                }
+                // end of section returns to the parent
+                if let Some(name) = tag.strip_prefix('/') {
+                    return match section {
+                        Some(open) if open == name => Ok(segments),
+                        _ => Err(format!("Unexpected '{{/{}}}' in template", name)),
+                    };
+                }
+                if let Some(name) = tag.strip_prefix('?').or_else(|| tag.strip_prefix('!')) {
+                    check_field(name)?;
+                    let body = parse_segments(chars, Some(name))?;
+                    segments.push(Segment::Section { name: name.to_string(), negate: tag.starts_with('!'), body });
+                    continue;
+                }
+                let (name, spec) = tag.split_once(':').unwrap_or((tag.as_str(), ""));
+                check_field(name)?;
+                check_spec(name, spec)?;
+                segments.push(Segment::Field { name: name.to_string(), spec: spec.to_string() });
+            }
+            c => literal.push(c),
+        }
+    }
+    if let Some(name) = section {
+        return Err(format!("Section '{{?{}}}' is not closed with '{{/{}}}'", name, name));
+    }
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(literal));
+    }
+    Ok(segments)
+}
+
+fn check_field(name: &str) -> Result<(), String> {
+    if FIELDS.contains(&name) {
+        Ok(())
+    } else {
+        Err(format!("Unknown template field: {}. Use {}", name, FIELDS.join(", ")))
+    }
+}
+
+// format date with strftime spec, None if the spec needs time or zone that a date does not have
+fn format_date(date: NaiveDate, spec: &str) -> Option<String> {
+    let mut formatted = String::new();
+    write!(formatted, "{}", date.format(spec)).ok()?;
+    Some(formatted)
+}
+
+// date and time specs must be valid strftime, other specs are padding like "<20", ">8", "^10" or ".30".
+// date has no time or zone, so their specifiers are rejected for it
+fn check_spec(name: &str, spec: &str) -> Result<(), String> {
+    if spec.is_empty() {
+        return Ok(());
+    }
+    if name == "date" || name == "time" || name == "datetime" {
+        if StrftimeItems::new(spec).any(|item| item == Item::Error) {
+            return Err(format!("Invalid date format in template: {}", spec));
+        }
+        if name == "date" && format_date(NaiveDate::default(), spec).is_none() {
+            return Err(format!("Date format in template cannot have time or time zone: {}. Use datetime or time", spec));
+        }
+        return Ok(());
+    }
+    parse_padding(spec).map(|_| ())
+}
+
+// parse padding spec to (alignment, width, max width)
+fn parse_padding(spec: &str) -> Result<(char, usize, Option<usize>), String> {
+    let invalid = || format!("Invalid padding in template: {}. Use for example <20, >8, ^10 or .30", spec);
+    let (width_spec, max_spec) = match spec.split_once('.') {
+        Some((width, max)) => (width, Some(max)),
+        None => (spec, None),
+    };
+    let (align, width) = match width_spec.chars().next() {
+        Some(align @ ('<' | '>' | '^')) => (align, &width_spec[1..]),
+        _ => ('<', width_spec),
+    };
+    let width = if width.is_empty() { 0 } else { width.parse().map_err(|_| invalid())? };
+    let max = match max_spec {
+        Some(max) => Some(max.parse().map_err(|_| invalid())?),
+        None => None,
+    };
+    Ok((align, width, max))
+}
+
+// value of the field, date and time formatted with the spec. Datetime of an event without time is
+// only the date, as the spec may have time specifiers
+fn field_value(name: &str, spec: &str, event: &Event, zone: &ViewZone) -> String {
+    let date_format = if spec.is_empty() { "%Y-%m-%d" } else { spec };
+    match name {
+        "date" => format_date(event.date_in(zone), date_format).unwrap_or_default(),
+        "time" => event.time().map(|time| zone.format(time, if spec.is_empty() { "%H:%M" } else { spec })).unwrap_or_default(),
+        "datetime" => match event.time() {
+            Some(time) => zone.format(time, if spec.is_empty() { "%Y-%m-%d %H:%M %Z" } else { spec }),
+            None => format_date(event.date(), "%Y-%m-%d").unwrap_or_default(),
+        },
+        "timezone" => event.timezone().to_string(),
+        "description" => event.description().to_string(),
+        "category" => event.format_category(StringFormat::Csv),
+        "primary" => event.primary_category().to_string(),
+        "secondary" => event.secondary_category().to_string(),
+        "tags" => event.format_tags(&StringFormat::Print),
+        "id" => event.id(),
+        "weekday" => event.date_in(zone).format("%A").to_string(),
+        _ => String::new(),
+    }
+}
+
+fn pad(value: String, spec: &str) -> String {
+    let Ok((align, width, max)) = parse_padding(spec) else {
+        return value;
+    };
+    let value = match max {
+        Some(max) => truncate_to_width(&value, max),
+        None => value,
+    };
+    let missing = width.saturating_sub(value.width());
+    match align {
+        '>' => format!("{}{}", " ".repeat(missing), value),
+        '^' => format!("{}{}{}", " ".repeat(missing / 2), value, " ".repeat(missing - missing / 2)),
+        _ => format!("{}{}", value, " ".repeat(missing)),
+    }
+}
+
+fn render_segments(segments: &[Segment], event: &Event, zone: &ViewZone, output: &mut String) {
+    for segment in segments {
+        match segment {
+            Segment::Literal(text) => output.push_str(text),
+            Segment::Field { name, spec } if name == "date" || name == "time" || name == "datetime" => {
+                output.push_str(&field_value(name, spec, event, zone));
+            }
+            Segment::Field { name, spec } => output.push_str(&pad(field_value(name, "", event, zone), spec)),
+            Segment::Section { name, negate, body } => {
+                if field_value(name, "", event, zone).is_empty() == *negate {
+                    render_segments(body, event, zone, output);
+                }
+            }
+        }
+    }
+}
+
+// read named templates from csv with columns name,template. Missing file gives no templates
+pub fn read_templates(file_path: &str) -> Result<BTreeMap<String, String>, Error> {
+    let mut templates = BTreeMap::new();
+    if !Path::new(file_path).exists() {
+        return Ok(templates);
+    }
+    let file = File::open(file_path)?;
+    let mut rdr = ReaderBuilder::new().flexible(true).from_reader(file);
+    for result in rdr.records() {
+        // go through readings, note about error-lines, but still continue
+        match result {
+            Ok(record) => {
+                templates.insert(record.get(0).unwrap_or_default().trim().to_string(), record.get(1).unwrap_or_default().to_string());
+            }
+            Err(err) => eprintln!("Error reading templates record: {}", err),
+        }
+    }
+    Ok(templates)
+}
+
+// print all given vector events in order from oldest to latest with the template
+pub fn print_events_template(events: &mut [Event], template: &Template, zone: &ViewZone) {
+    events.sort();
+    for event in events.iter() {
+        println!("{}", template.render(event, zone));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn create_test_event(tags: &str) -> Event {
+        let date = NaiveDate::from_ymd_opt(2023, 11, 14).expect("Valid date");
+        Event::new(date, ".NET 8 released".to_string(), vec!["microsoft".to_string(), "dotnet".to_string()])
+            .with_tags(crate::utils::tags::parse_tags(tags))
+    }
+
+    #[test]
+    fn test_template_fields_and_date_format() {
+        let template = Template::parse("{date:%d.%m.%Y} [{category}] {description} ({primary})").unwrap();
+
+        assert_eq!(template.render(&create_test_event(""), &ViewZone::Local), "14.11.2023 [microsoft/dotnet] .NET 8 released (microsoft)");
+    }
+
+    #[test]
+    fn test_template_padding_and_sections() {
+        let template = Template::parse("{secondary:>8}|{description:.6}{?tags} {tags}{/tags}{!tags} -{/tags} {{x}}").unwrap();
+
+        assert_eq!(template.render(&create_test_event("lts"), &ViewZone::Local), "  dotnet|.NET … #lts {x}");
+        assert_eq!(template.render(&create_test_event(""), &ViewZone::Local), "  dotnet|.NET … - {x}");
+    }
+
+    #[test]
+    fn test_template_parse_errors() {
+        assert!(Template::parse("{colour}").is_err());
+        assert!(Template::parse("{date:%Q}").is_err());
+        assert!(Template::parse("{?tags} open").is_err());
+        assert!(Template::parse("{description:<x}").is_err());
+        // date has no hours
+        assert!(Template::parse("{date:%H}").is_err());
+        assert!(Template::parse("{date:%d.%m.%Y %Z}").is_err());
+    }
+
+    #[test]
+    fn test_template_datetime_without_time() {
+        let template = Template::parse("{datetime:%d.%m.%Y %H:%M}|{time:%H}").unwrap();
+        assert_eq!(template.render(&create_test_event(""), &ViewZone::Local), "2023-11-14|");
+    }
+}
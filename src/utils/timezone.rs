@@ -0,0 +1,99 @@
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+
+// zone the events are shown in: system local zone unless overridden with --tz
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum ViewZone {
+    #[default]
+    Local,
+    Named(Tz),
+}
+
+impl ViewZone {
+    // parse IANA zone name, for example "Europe/Helsinki"
+    pub fn parse(name: &str) -> Result<Self, String> {
+        parse_zone(name).map(ViewZone::Named)
+    }
+
+    // todays date as seen in this zone
+    pub fn today(&self) -> NaiveDate {
+        self.today_at(Utc::now())
+    }
+
+    // date of the given moment in this zone, used so "today" flips at midnight of the viewer
+    pub fn today_at(&self, now: DateTime<Utc>) -> NaiveDate {
+        match self {
+            ViewZone::Local => now.with_timezone(&Local).date_naive(),
+            ViewZone::Named(tz) => now.with_timezone(tz).date_naive(),
+        }
+    }
+
+    // date of the given time in this zone
+    pub fn date_of(&self, time: &DateTime<FixedOffset>) -> NaiveDate {
+        self.today_at(time.with_timezone(&Utc))
+    }
+
+    // format time converted to this zone
+    pub fn format(&self, time: &DateTime<FixedOffset>, format: &str) -> String {
+        match self {
+            ViewZone::Local => time.with_timezone(&Local).format(format).to_string(),
+            ViewZone::Named(tz) => time.with_timezone(tz).format(format).to_string(),
+        }
+    }
+}
+
+// parse IANA zone name with readable error
+pub fn parse_zone(name: &str) -> Result<Tz, String> {
+    name.trim().parse::<Tz>().map_err(|_| format!("Unknown time zone: {}", name))
+}
+
+// parse time of day in format HH:MM or HH:MM:SS
+pub fn parse_time(time_str: &str) -> Result<NaiveTime, String> {
+    NaiveTime::parse_from_str(time_str, "%H:%M")
+        .or_else(|_| NaiveTime::parse_from_str(time_str, "%H:%M:%S"))
+        .map_err(|_| format!("Invalid time: {}. Use format HH:MM", time_str))
+}
+
+// combine date and time in given zone, or in system local zone if no zone name is given
+pub fn resolve_time(date: NaiveDate, time: NaiveTime, zone_name: &str) -> Result<DateTime<FixedOffset>, String> {
+    let naive = date.and_time(time);
+    let resolved = if zone_name.is_empty() {
+        Local.from_local_datetime(&naive).earliest().map(|time| time.fixed_offset())
+    } else {
+        let tz = parse_zone(zone_name)?;
+        tz.from_local_datetime(&naive).earliest().map(|time| time.fixed_offset())
+    };
+    // time can be skipped on daylight saving change
+    resolved.ok_or_else(|| format!("Time {} does not exist in the time zone", naive))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_today_at_crosses_midnight_in_named_zone() {
+        // 23:30 UTC is already next day in Helsinki, but not in New York
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 23, 30, 0).unwrap();
+        let helsinki = ViewZone::parse("Europe/Helsinki").unwrap();
+        let new_york = ViewZone::parse("America/New_York").unwrap();
+
+        assert_eq!(helsinki.today_at(now), NaiveDate::from_ymd_opt(2024, 1, 2).unwrap());
+        assert_eq!(new_york.today_at(now), NaiveDate::from_ymd_opt(2024, 1, 1).unwrap());
+    }
+
+    #[test]
+    fn test_resolve_time_uses_zone_offset() {
+        let date = NaiveDate::from_ymd_opt(2024, 7, 1).unwrap();
+        let time = parse_time("18:00").unwrap();
+        let resolved = resolve_time(date, time, "Europe/Helsinki").unwrap();
+
+        assert_eq!(resolved.to_rfc3339(), "2024-07-01T18:00:00+03:00");
+    }
+
+    #[test]
+    fn test_parse_zone_unknown() {
+        assert!(ViewZone::parse("Europe/Tampere").is_err());
+        assert!(parse_time("25:00").is_err());
+    }
+}